@@ -35,6 +35,38 @@ fn add_slices_lazy(out: &mut [f64], a: &[f64], b: &[f64]) {
         .for_each(|((o, a), b)| *o = *a + *b);
 }
 
+#[inline(never)]
+fn dot_std(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).fold(0.0, |acc, (a, b)| acc + a * b)
+}
+
+#[inline(never)]
+fn dot_eager(a: &[f64], b: &[f64]) -> f64 {
+    zip_eq::num::dot(a, b)
+}
+
+#[inline(never)]
+fn axpy_std(alpha: f64, x: &[f64], y: &mut [f64]) {
+    y.iter_mut().zip(x).for_each(|(y, x)| *y += alpha * x);
+}
+
+#[inline(never)]
+fn axpy_eager(alpha: f64, x: &[f64], y: &mut [f64]) {
+    zip_eq::num::axpy(alpha, x, y);
+}
+
+#[inline(never)]
+fn squared_distance_std(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .fold(0.0, |acc, (a, b)| acc + (a - b) * (a - b))
+}
+
+#[inline(never)]
+fn squared_distance_eager(a: &[f64], b: &[f64]) -> f64 {
+    zip_eq::num::squared_distance(a, b)
+}
+
 #[inline(never)]
 fn add_slices_std_chunked(out: IterMut<'_, f64>, a: Iter<'_, f64>, b: Iter<'_, f64>) {
     out.zip(a).zip(b).for_each(|((o, a), b)| *o = *a + *b);
@@ -47,6 +79,12 @@ fn add_slices_zip_chunked(out: IterMut<'_, f64>, a: Iter<'_, f64>, b: Iter<'_, f
         .for_each(|((o, a), b)| *o = *a + *b);
 }
 
+#[inline(never)]
+fn add_slices_zip_segmented(out: IterMut<'_, f64>, a: Iter<'_, f64>, b: Iter<'_, f64>) {
+    out.zip_eq_eager(a.zip_eq_eager(b))
+        .for_each_segmented(|(o, (a, b))| *o = *a + *b);
+}
+
 #[inline(never)]
 fn add_slices_zip_lazy_chunked(out: IterMut<'_, f64>, a: Iter<'_, f64>, b: Iter<'_, f64>) {
     out.zip_eq_lazy(a)
@@ -70,6 +108,11 @@ unsafe fn add_chars_eager(a: Chars<'_>, b: Chars<'_>) -> u32 {
     unsafe { a.zip_eq_unchecked(b) }.fold(0, |acc, (a, b)| acc + a as u32 + b as u32)
 }
 
+#[inline(never)]
+fn add_chars_checked(a: &str, b: &str) -> u32 {
+    zip_eq::zip_eq_chars(a, b).fold(0, |acc, (a, b)| acc + a as u32 + b as u32)
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let n = 0x1000;
 
@@ -87,6 +130,36 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| add_slices_lazy(black_box(&mut out), black_box(&lhs), black_box(&rhs)))
     });
 
+    c.bench_function("dot std", |b| {
+        b.iter(|| black_box(dot_std(black_box(&lhs), black_box(&rhs))))
+    });
+    c.bench_function("dot eager", |b| {
+        b.iter(|| black_box(dot_eager(black_box(&lhs), black_box(&rhs))))
+    });
+    c.bench_function("dot pairwise", |b| {
+        b.iter(|| black_box(zip_eq::num::dot_pairwise(black_box(&lhs), black_box(&rhs))))
+    });
+    c.bench_function("dot compensated", |b| {
+        b.iter(|| {
+            black_box(zip_eq::num::dot_compensated(
+                black_box(&lhs),
+                black_box(&rhs),
+            ))
+        })
+    });
+    c.bench_function("axpy std", |b| {
+        b.iter(|| axpy_std(black_box(2.0), black_box(&lhs), black_box(&mut out)))
+    });
+    c.bench_function("axpy eager", |b| {
+        b.iter(|| axpy_eager(black_box(2.0), black_box(&lhs), black_box(&mut out)))
+    });
+    c.bench_function("squared distance std", |b| {
+        b.iter(|| black_box(squared_distance_std(black_box(&lhs), black_box(&rhs))))
+    });
+    c.bench_function("squared distance eager", |b| {
+        b.iter(|| black_box(squared_distance_eager(black_box(&lhs), black_box(&rhs))))
+    });
+
     let mut out: VecDeque<_> = vec![0.0; n].into();
     let lhs: VecDeque<_> = vec![0.0; n].into();
     let rhs: VecDeque<_> = vec![0.0; n].into();
@@ -109,6 +182,15 @@ fn criterion_benchmark(c: &mut Criterion) {
             )
         })
     });
+    c.bench_function("chunks segmented", |b| {
+        b.iter(|| {
+            add_slices_zip_segmented(
+                black_box(out.iter_mut()),
+                black_box(lhs.iter()),
+                black_box(rhs.iter()),
+            )
+        })
+    });
     c.bench_function("chunks lazy", |b| {
         b.iter(|| {
             add_slices_zip_lazy_chunked(
@@ -126,6 +208,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("unknown len eager", |b| {
         b.iter(|| black_box(unsafe { add_chars_eager(black_box(s.chars()), black_box(s.chars())) }))
     });
+    c.bench_function("unknown len checked", |b| {
+        b.iter(|| black_box(add_chars_checked(black_box(&s), black_box(&s))))
+    });
     c.bench_function("unknown len lazy", |b| {
         b.iter(|| black_box(add_chars_lazy(black_box(s.chars()), black_box(s.chars()))))
     });
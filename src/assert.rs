@@ -0,0 +1,274 @@
+use core::fmt::{self, Debug, Write};
+use std::collections::VecDeque;
+
+use super::{Difference, Side};
+
+/// Number of equal elements shown before and after the first difference by
+/// [`assert_iter_eq!`](crate::assert_iter_eq).
+const CONTEXT: usize = 3;
+
+/// Asserts that all the arguments have the same length, printing the expression and the length
+/// of each of them if they don't.
+///
+/// The arguments can be [`ExactSizeIterator`]s, references to collections, or collections whose
+/// references can be turned into one, such as arrays, `Vec`s or maps. These are only borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::assert_same_len;
+///
+/// let ids = vec![1, 2, 3];
+/// let names = ["a", "b", "c"];
+/// assert_same_len!(ids, names, (0..3).map(|i| i * 2));
+/// assert_eq!(ids.len(), 3);
+/// ```
+///
+/// ```should_panic
+/// let ids = vec![1, 2, 3];
+/// let names = ["a", "b"];
+/// // panics with:
+/// // assertion failed: `assert_same_len!(ids, names)`
+/// //    ids: 3
+/// //  names: 2
+/// zip_eq::assert_same_len!(ids, names);
+/// ```
+#[macro_export]
+macro_rules! assert_same_len {
+    ($first:expr, $($rest:expr),+ $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{CopyIntoIterLen as _, ExactSizeLen as _, RefIntoIterLen as _};
+        $crate::__private::assert_same_len(
+            ::core::stringify!($first, $($rest),+),
+            &[::core::stringify!($first), $(::core::stringify!($rest)),+],
+            &[
+                (&&$crate::__private::Probe(&$first)).len_probe(),
+                $((&&$crate::__private::Probe(&$rest)).len_probe()),+
+            ],
+        );
+    }};
+}
+
+/// Asserts that all the arguments have the same length, like
+/// [`assert_same_len!`](crate::assert_same_len), but only when debug assertions are enabled.
+///
+/// # Examples
+///
+/// ```
+/// let a = [1, 2];
+/// let b = vec![3, 4];
+/// zip_eq::debug_assert_same_len!(a, b);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_same_len {
+    ($($arg:tt)*) => {
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_same_len!($($arg)*);
+        }
+    };
+}
+
+/// Asserts that two sequences have equal elements and the same length.
+///
+/// On failure, the message shows the expressions and their lengths, the first difference as
+/// reported by [`first_difference`](crate::first_difference), and the elements around it. Like
+/// [`assert_eq!`], it accepts an optional message after the arguments.
+///
+/// The arguments are consumed with [`IntoIterator`], so pass references to keep them.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::assert_iter_eq;
+///
+/// let squares = vec![0, 1, 4, 9];
+/// assert_iter_eq!(&squares, (0..4).map(|i| i * i).collect::<Vec<_>>().iter());
+/// assert_iter_eq!(squares, [0, 1, 4, 9], "squares of {}..{}", 0, 4);
+/// ```
+///
+/// ```should_panic
+/// // panics with:
+/// // assertion failed: `left == right`: elements at index 4 differ: left is 4 but right is 5
+/// //   left: `0..8` (8 elements)
+/// //  right: `[0, 1, 2, 3, 5, 6, 7]` (7 elements)
+/// //    1: 1 | 1
+/// //    2: 2 | 2
+/// //    3: 3 | 3
+/// //  > 4: 4 | 5
+/// //    5: 5 | 6
+/// //    6: 6 | 7
+/// //    7: 7 | (end)
+/// zip_eq::assert_iter_eq!(0..8, [0, 1, 2, 3, 5, 6, 7]);
+/// ```
+#[macro_export]
+macro_rules! assert_iter_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__private::assert_iter_eq(
+            $left,
+            $right,
+            ::core::stringify!($left),
+            ::core::stringify!($right),
+            ::core::option::Option::None,
+        )
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::__private::assert_iter_eq(
+            $left,
+            $right,
+            ::core::stringify!($left),
+            ::core::stringify!($right),
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Support code for the assertion macros, not part of the public API.
+pub mod __private {
+    use super::*;
+
+    /// Wraps a macro argument to pick how its length is computed, by autoref specialization:
+    /// [`RefIntoIterLen`] applies first, then [`ExactSizeLen`], then [`CopyIntoIterLen`].
+    pub struct Probe<'a, T: ?Sized>(pub &'a T);
+
+    /// Length of values whose references are [`IntoIterator`]s of known length.
+    pub trait RefIntoIterLen {
+        fn len_probe(&self) -> usize;
+    }
+
+    impl<T: ?Sized> RefIntoIterLen for &Probe<'_, T>
+    where
+        for<'b> &'b T: IntoIterator,
+        for<'b> <&'b T as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        fn len_probe(&self) -> usize {
+            self.0.into_iter().len()
+        }
+    }
+
+    /// Length of [`ExactSizeIterator`]s.
+    pub trait ExactSizeLen {
+        fn len_probe(&self) -> usize;
+    }
+
+    impl<T: ExactSizeIterator + ?Sized> ExactSizeLen for &&Probe<'_, T> {
+        fn len_probe(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    /// Length of references to collections, which are [`IntoIterator`]s of known length.
+    pub trait CopyIntoIterLen {
+        fn len_probe(&self) -> usize;
+    }
+
+    impl<T> CopyIntoIterLen for Probe<'_, T>
+    where
+        T: Copy + IntoIterator,
+        T::IntoIter: ExactSizeIterator,
+    {
+        fn len_probe(&self) -> usize {
+            self.0.into_iter().len()
+        }
+    }
+
+    #[track_caller]
+    pub fn assert_same_len(args: &str, names: &[&str], lens: &[usize]) {
+        if lens.iter().all(|&len| len == lens[0]) {
+            return;
+        }
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        let mut msg = format!("assertion failed: `assert_same_len!({args})`");
+        for (name, len) in names.iter().zip(lens) {
+            let _ = write!(msg, "\n {name:>width$}: {len}");
+        }
+        panic!("{msg}");
+    }
+
+    #[track_caller]
+    pub fn assert_iter_eq<A, B>(
+        left: A,
+        right: B,
+        left_expr: &str,
+        right_expr: &str,
+        args: Option<fmt::Arguments<'_>>,
+    ) where
+        A: IntoIterator,
+        B: IntoIterator,
+        A::Item: PartialEq<B::Item> + Debug,
+        B::Item: Debug,
+    {
+        let mut a = left.into_iter().fuse();
+        let mut b = right.into_iter().fuse();
+        let mut rows = VecDeque::with_capacity(2 * CONTEXT + 1);
+        let mut index = 0;
+        let (l, r) = loop {
+            match (a.next(), b.next()) {
+                (None, None) => return,
+                (Some(l), Some(r)) if l == r => {
+                    if rows.len() == CONTEXT {
+                        rows.pop_front();
+                    }
+                    rows.push_back((Some(l), Some(r)));
+                    index += 1;
+                }
+                (l, r) => break (l, r),
+            }
+        };
+
+        let first = index - rows.len();
+        let mut left_len = index + l.is_some() as usize;
+        let mut right_len = index + r.is_some() as usize;
+        rows.push_back((l, r));
+        for _ in 0..CONTEXT {
+            let (l, r) = (a.next(), b.next());
+            if l.is_none() && r.is_none() {
+                break;
+            }
+            left_len += l.is_some() as usize;
+            right_len += r.is_some() as usize;
+            rows.push_back((l, r));
+        }
+        left_len += a.count();
+        right_len += b.count();
+
+        let diff = match &rows[index - first] {
+            (Some(left), Some(right)) => Difference::ValueMismatch { index, left, right },
+            _ => Difference::LengthMismatch {
+                index,
+                longer_side: if left_len > right_len {
+                    Side::Left
+                } else {
+                    Side::Right
+                },
+                extra: left_len.abs_diff(right_len),
+            },
+        };
+        let mut msg = format!("assertion failed: `left == right`: {diff}");
+        if let Some(args) = args {
+            let _ = write!(msg, ": {args}");
+        }
+        let _ = write!(
+            msg,
+            "\n  left: `{left_expr}` ({left_len} elements)\n right: `{right_expr}` ({right_len} elements)"
+        );
+        let width = (first + rows.len() - 1).to_string().len();
+        for (i, (l, r)) in (first..).zip(&rows) {
+            let marker = if i == index { ">" } else { " " };
+            let _ = write!(msg, "\n {marker} {i:>width$}: {} | {}", Item(l), Item(r));
+        }
+        panic!("{msg}");
+    }
+}
+
+/// An element in the window printed by [`assert_iter_eq!`](crate::assert_iter_eq), or the end of
+/// its input.
+struct Item<'a, T>(&'a Option<T>);
+
+impl<T: Debug> fmt::Display for Item<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(item) => write!(f, "{item:?}"),
+            None => f.write_str("(end)"),
+        }
+    }
+}
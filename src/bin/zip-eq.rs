@@ -0,0 +1,270 @@
+//! `zip-eq`: joins files line by line like `paste`, and fails if they don't have the same number
+//! of lines.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process::ExitCode;
+
+use zip_eq::zip_eq_all;
+
+const USAGE: &str = "\
+usage: zip-eq [OPTIONS] FILE FILE...
+
+Joins the lines of the FILEs, which must all have the same number of lines.
+A FILE of `-` reads from the standard input.
+
+options:
+  -d, --delimiter DELIM  separate the joined lines with DELIM instead of a tab
+  -c, --check            only check that the FILEs have the same number of lines
+  -z, --null             split and join records separated by NUL instead of newlines
+  -h, --help             print this message
+
+exit status: 0 on success, 1 if the FILEs have different lengths, 2 on other errors";
+
+struct Options {
+    delimiter: Vec<u8>,
+    check: bool,
+    null: bool,
+    files: Vec<OsString>,
+}
+
+#[derive(Debug)]
+enum Failure {
+    Help,
+    Usage(String),
+    Io(String),
+    Mismatch(String),
+}
+
+fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Options, Failure> {
+    let mut options = Options {
+        delimiter: b"\t".to_vec(),
+        check: false,
+        null: false,
+        files: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("-h" | "--help") => return Err(Failure::Help),
+            Some("-c" | "--check") => options.check = true,
+            Some("-z" | "--null") => options.null = true,
+            Some("-d" | "--delimiter") => match args.next() {
+                Some(delimiter) => options.delimiter = delimiter.into_encoded_bytes(),
+                None => return Err(Failure::Usage(format!("{arg:?} requires a value"))),
+            },
+            Some(s) if s.starts_with("--delimiter=") => {
+                options.delimiter = s.as_bytes()["--delimiter=".len()..].to_vec();
+            }
+            Some("--") => {
+                options.files.extend(args);
+                break;
+            }
+            Some(s) if s.starts_with('-') && s != "-" => {
+                return Err(Failure::Usage(format!("unknown option {s:?}")));
+            }
+            _ => options.files.push(arg),
+        }
+    }
+    if options.files.len() < 2 {
+        return Err(Failure::Usage("at least two files are required".into()));
+    }
+    if options.files.iter().filter(|file| *file == "-").count() > 1 {
+        return Err(Failure::Usage(
+            "the standard input can only be given once".into(),
+        ));
+    }
+    Ok(options)
+}
+
+fn name(file: &OsString) -> String {
+    if file == "-" {
+        "<stdin>".into()
+    } else {
+        file.to_string_lossy().into_owned()
+    }
+}
+
+fn open(file: &OsString) -> Result<Box<dyn BufRead>, Failure> {
+    if file == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    match File::open(file) {
+        Ok(f) => Ok(Box::new(BufReader::new(f))),
+        Err(err) => Err(Failure::Io(format!("{}: {err}", name(file)))),
+    }
+}
+
+fn run(options: &Options, out: impl Write) -> Result<(), Failure> {
+    let (separator, unit) = if options.null {
+        (b'\0', "record")
+    } else {
+        (b'\n', "line")
+    };
+    let readers = options
+        .files
+        .iter()
+        .map(open)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut rows = zip_eq_all(readers.into_iter().map(|reader| reader.split(separator)));
+    let mut out = BufWriter::new(out);
+    let write_err = |err: io::Error| Failure::Io(format!("<stdout>: {err}"));
+
+    loop {
+        let row = match rows.try_next() {
+            Ok(Some(row)) => row,
+            Ok(None) => break,
+            Err(mismatch) => {
+                return Err(Failure::Mismatch(format!(
+                    "{} ended before {unit} {} but {} continues",
+                    name(&options.files[mismatch.ended()]),
+                    mismatch.index() + 1,
+                    name(&options.files[mismatch.continued()]),
+                )))
+            }
+        };
+        for (i, (record, file)) in row.into_iter().zip(&options.files).enumerate() {
+            let record = record.map_err(|err| Failure::Io(format!("{}: {err}", name(file))))?;
+            if options.check {
+                continue;
+            }
+            if i > 0 {
+                out.write_all(&options.delimiter).map_err(write_err)?;
+            }
+            out.write_all(&record).map_err(write_err)?;
+        }
+        if !options.check {
+            out.write_all(&[separator]).map_err(write_err)?;
+        }
+    }
+    out.flush().map_err(write_err)
+}
+
+/// Returns the exit status for `failure`, as described in the usage message.
+fn status(failure: &Failure) -> u8 {
+    match failure {
+        Failure::Help => 0,
+        Failure::Mismatch(_) => 1,
+        Failure::Usage(_) | Failure::Io(_) => 2,
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_args(std::env::args_os().skip(1))
+        .and_then(|options| run(&options, io::stdout().lock()));
+    let Err(failure) = result else {
+        return ExitCode::SUCCESS;
+    };
+    match &failure {
+        Failure::Help => println!("{USAGE}"),
+        Failure::Usage(msg) => eprintln!("zip-eq: {msg}\n\n{USAGE}"),
+        Failure::Io(msg) | Failure::Mismatch(msg) => eprintln!("zip-eq: {msg}"),
+    }
+    ExitCode::from(status(&failure))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = OsString> {
+        args.iter()
+            .map(OsString::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Writes `contents` to a file named after the test, and returns its path.
+    fn file(name: &str, contents: &[u8]) -> OsString {
+        let mut path = std::env::temp_dir();
+        path.push(format!("zip-eq-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path.into_os_string()
+    }
+
+    fn output(options: &Options) -> Result<String, Failure> {
+        let mut out = Vec::new();
+        run(options, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn options(flags: &[&str], files: &[&OsString]) -> Options {
+        let files = files.iter().map(|file| file.to_str().unwrap());
+        let all: Vec<_> = flags.iter().copied().chain(files).collect();
+        parse_args(args(&all)).unwrap()
+    }
+
+    #[test]
+    fn join() {
+        let a = file("join-a", b"1\n2\n");
+        let b = file("join-b", b"x\ny\n");
+        assert_eq!(output(&options(&[], &[&a, &b])).unwrap(), "1\tx\n2\ty\n");
+    }
+
+    #[test]
+    fn delimiter() {
+        let a = file("delimiter-a", b"1\n2\n");
+        let b = file("delimiter-b", b"x\ny\n");
+        let c = file("delimiter-c", b"p\nq\n");
+        let joined = output(&options(&["-d", ", "], &[&a, &b, &c])).unwrap();
+        assert_eq!(joined, "1, x, p\n2, y, q\n");
+        let joined = output(&options(&["--delimiter=;"], &[&a, &b])).unwrap();
+        assert_eq!(joined, "1;x\n2;y\n");
+    }
+
+    #[test]
+    fn check() {
+        let a = file("check-a", b"1\n2\n");
+        let b = file("check-b", b"x\ny\n");
+        assert_eq!(output(&options(&["--check"], &[&a, &b])).unwrap(), "");
+    }
+
+    #[test]
+    fn null() {
+        let a = file("null-a", b"1\n2\x003\x00");
+        let b = file("null-b", b"x\x00y\x00");
+        let joined = output(&options(&["-z"], &[&a, &b])).unwrap();
+        assert_eq!(joined, "1\n2\tx\x003\ty\x00");
+    }
+
+    #[test]
+    fn mismatch() {
+        let a = file("mismatch-a", b"1\n2\n3\n");
+        let b = file("mismatch-b", b"x\ny\n");
+        for flags in [&[][..], &["-c"]] {
+            let Err(failure) = output(&options(flags, &[&a, &b])) else {
+                panic!("the lengths should differ");
+            };
+            assert_eq!(status(&failure), 1);
+            let Failure::Mismatch(msg) = failure else {
+                unreachable!()
+            };
+            let expected = format!(
+                "{} ended before line 3 but {} continues",
+                b.to_string_lossy(),
+                a.to_string_lossy(),
+            );
+            assert_eq!(msg, expected);
+        }
+    }
+
+    #[test]
+    fn usage() {
+        for bad in [
+            &["only-one"][..],
+            &["a", "b", "--unknown"],
+            &["a", "b", "-d"],
+            &["-", "b", "-"],
+        ] {
+            let Err(failure) = parse_args(args(bad)) else {
+                panic!("{bad:?} should be rejected");
+            };
+            assert!(matches!(failure, Failure::Usage(_)));
+            assert_eq!(status(&failure), 2);
+        }
+        let Err(failure) = parse_args(args(&["-h"])) else {
+            panic!("-h should print the help");
+        };
+        assert_eq!(status(&failure), 0);
+    }
+}
@@ -0,0 +1,170 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+use super::{KeyMismatch, KeyZipError, LengthMismatch, ZipEq, ZipEqLazyCheck};
+
+/// Iterator that zips two iterators, checking during iteration that they have the same length
+/// and that the elements at each position have the same key. It is created by
+/// [`zip_eq_by_key`].
+#[derive(Debug, Clone)]
+pub struct ZipEqByKey<A, B, FA, FB> {
+    zipped: ZipEqLazyCheck<A, B>,
+    key_a: FA,
+    key_b: FB,
+    index: usize,
+    done: bool,
+}
+
+/// Returns an iterator that zips `a` and `b`, checking that they have the same length and that
+/// `key_a` and `key_b` return the same key for the elements at each position.
+///
+/// This is meant for inputs that describe the same entities in the same order, such as two
+/// outputs sorted by the same identifier.
+/// # Panics
+/// Iterating panics if one of the inputs ends before the other, or if the keys of two elements
+/// differ. Use [`ZipEqByKey::try_next`] to get a [`KeyZipError`] instead.
+///
+/// # Examples
+///
+/// ```
+/// let names = [(1, "ada"), (2, "bob")];
+/// let scores = [(1, 9.5), (2, 7.0)];
+/// let joined: Vec<_> = zip_eq::zip_eq_by_key(names, scores, |n| n.0, |s| s.0)
+///     .map(|((id, name), (_, score))| (id, name, score))
+///     .collect();
+///
+/// assert_eq!(joined, [(1, "ada", 9.5), (2, "bob", 7.0)]);
+/// ```
+///
+/// ```should_panic
+/// use zip_eq::ZipEq;
+///
+/// let names = [(1, "ada"), (2, "bob")];
+/// let scores = [(1, 9.5), (3, 7.0)];
+/// // panics with "ZipEq: keys at index 1 differ: names has 2 but scores has 3"
+/// zip_eq::zip_eq_by_key(names.label("names"), scores.label("scores"), |n| n.0, |s| s.0)
+///     .for_each(drop);
+/// ```
+pub fn zip_eq_by_key<A, B, K, FA, FB>(
+    a: A,
+    b: B,
+    key_a: FA,
+    key_b: FB,
+) -> ZipEqByKey<A::IntoIter, B::IntoIter, FA, FB>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+    K: PartialEq,
+    FA: FnMut(&A::Item) -> K,
+    FB: FnMut(&B::Item) -> K,
+{
+    ZipEqByKey {
+        zipped: a.zip_eq_lazy(b),
+        key_a,
+        key_b,
+        index: 0,
+        done: false,
+    }
+}
+
+impl<A, B, K, FA, FB> ZipEqByKey<A, B, FA, FB>
+where
+    A: Iterator,
+    B: Iterator,
+    K: PartialEq,
+    FA: FnMut(&A::Item) -> K,
+    FB: FnMut(&B::Item) -> K,
+{
+    /// Returns the next pair of elements, or an error if one of the inputs ended before the
+    /// other or if the keys of the elements differ. The iteration ends after the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::KeyZipError;
+    ///
+    /// let first_byte = |s: &&str| s.as_bytes()[0];
+    /// let mut zipped = zip_eq::zip_eq_by_key(["a1", "b2"], ["a", "c"], first_byte, first_byte);
+    ///
+    /// assert_eq!(zipped.try_next(), Ok(Some(("a1", "a"))));
+    /// let Err(KeyZipError::Key(err)) = zipped.try_next() else { panic!() };
+    /// assert_eq!((err.index(), err.into_keys()), (1, (b'b', b'c')));
+    /// assert_eq!(zipped.try_next(), Ok(None));
+    /// ```
+    pub fn try_next(
+        &mut self,
+    ) -> Result<Option<<ZipEqLazyCheck<A, B> as Iterator>::Item>, KeyZipError<K>> {
+        if self.done {
+            return Ok(None);
+        }
+        let [left, right] = self.zipped.labels();
+        let (a, b) = match self.zipped.try_next() {
+            Ok(Some(pair)) => pair,
+            Ok(None) => {
+                self.done = true;
+                return Ok(None);
+            }
+            Err(longer) => {
+                self.done = true;
+                let err = LengthMismatch::at(self.index, longer).with_labels(left, right);
+                return Err(KeyZipError::Length(err));
+            }
+        };
+        let (key_a, key_b) = ((self.key_a)(&a), (self.key_b)(&b));
+        if key_a != key_b {
+            self.done = true;
+            let err = KeyMismatch::new(self.index, key_a, key_b).with_labels(left, right);
+            return Err(KeyZipError::Key(err));
+        }
+        self.index += 1;
+        Ok(Some((a, b)))
+    }
+
+    /// Returns the inputs, with the elements that were already yielded consumed.
+    pub fn into_inner(self) -> (A, B) {
+        (self.zipped.a, self.zipped.b)
+    }
+}
+
+#[cold]
+fn panic_key_mismatch<K: fmt::Debug>(err: KeyZipError<K>) -> ! {
+    match err {
+        KeyZipError::Length(err) => super::panic_len_mismatch(err),
+        KeyZipError::Key(err) => panic!("ZipEq: {err}"),
+    }
+}
+
+impl<A, B, K, FA, FB> Iterator for ZipEqByKey<A, B, FA, FB>
+where
+    A: Iterator,
+    B: Iterator,
+    K: PartialEq + fmt::Debug,
+    FA: FnMut(&A::Item) -> K,
+    FB: FnMut(&B::Item) -> K,
+{
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(pair) => pair,
+            Err(err) => panic_key_mismatch(err),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        self.zipped.size_hint()
+    }
+}
+
+impl<A, B, K, FA, FB> FusedIterator for ZipEqByKey<A, B, FA, FB>
+where
+    A: Iterator,
+    B: Iterator,
+    K: PartialEq + fmt::Debug,
+    FA: FnMut(&A::Item) -> K,
+    FB: FnMut(&B::Item) -> K,
+{
+}
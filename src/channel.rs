@@ -0,0 +1,180 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use super::{LengthMismatch, Side, ZipEq, ZipEqLazyCheck};
+
+/// Returns a blocking iterator that pairs the messages of two channels, checking that both
+/// channels yield the same number of messages.
+///
+/// Each call to `next` blocks until both channels have a message, or until their senders are
+/// disconnected.
+/// # Panics
+/// Iterating panics if all the senders of one channel are disconnected while the other channel
+/// still has messages.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// let (tx_a, rx_a) = mpsc::channel();
+/// let (tx_b, rx_b) = mpsc::channel();
+/// thread::spawn(move || {
+///     for i in 0..3 {
+///         tx_a.send(i).unwrap();
+///         tx_b.send(i * i).unwrap();
+///     }
+/// });
+///
+/// let pairs: Vec<_> = zip_eq::zip_eq_receivers(rx_a, rx_b).collect();
+/// assert_eq!(pairs, [(0, 0), (1, 1), (2, 4)]);
+/// ```
+pub fn zip_eq_receivers<A, B>(
+    a: Receiver<A>,
+    b: Receiver<B>,
+) -> ZipEqLazyCheck<mpsc::IntoIter<A>, mpsc::IntoIter<B>> {
+    a.zip_eq_lazy(b)
+}
+
+/// Error yielded by [`ReceiversZipEqTimeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecvZipError {
+    /// No message was received from the channel on this side before the timeout. The message
+    /// received from the other side, if any, is kept until the next call to `next`.
+    Timeout(Side),
+    /// The senders of one channel are disconnected while the other channel still has messages.
+    Length(LengthMismatch),
+}
+
+impl fmt::Display for RecvZipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvZipError::Timeout(side) => write!(f, "timed out waiting on the {side} channel"),
+            RecvZipError::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RecvZipError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecvZipError::Timeout(_) => None,
+            RecvZipError::Length(err) => Some(err),
+        }
+    }
+}
+
+/// Iterator that pairs the messages of two channels with a timeout, created by
+/// [`zip_eq_receivers_timeout`].
+#[derive(Debug)]
+pub struct ReceiversZipEqTimeout<A, B> {
+    a: Receiver<A>,
+    b: Receiver<B>,
+    timeout: Duration,
+    a_msg: Option<Option<A>>,
+    index: usize,
+    done: bool,
+}
+
+/// Returns an iterator that pairs the messages of two channels, waiting at most `timeout` for
+/// each pair.
+///
+/// Timeouts are reported with [`RecvZipError::Timeout`] and iteration can resume afterwards.
+/// If the senders of one channel are disconnected while the other channel still has messages,
+/// [`RecvZipError::Length`] is yielded and the iteration ends.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::mpsc;
+/// use std::time::Duration;
+/// use zip_eq::{RecvZipError, Side};
+///
+/// let (tx_a, rx_a) = mpsc::channel();
+/// let (tx_b, rx_b) = mpsc::channel();
+/// tx_a.send(1).unwrap();
+///
+/// let mut zipped = zip_eq::zip_eq_receivers_timeout(rx_a, rx_b, Duration::from_millis(1));
+/// assert_eq!(zipped.next(), Some(Err(RecvZipError::Timeout(Side::Right))));
+///
+/// tx_b.send(2).unwrap();
+/// assert_eq!(zipped.next(), Some(Ok((1, 2))));
+///
+/// drop(tx_b);
+/// tx_a.send(3).unwrap();
+/// assert!(matches!(zipped.next(), Some(Err(RecvZipError::Length(_)))));
+/// assert_eq!(zipped.next(), None);
+/// ```
+pub fn zip_eq_receivers_timeout<A, B>(
+    a: Receiver<A>,
+    b: Receiver<B>,
+    timeout: Duration,
+) -> ReceiversZipEqTimeout<A, B> {
+    ReceiversZipEqTimeout {
+        a,
+        b,
+        timeout,
+        a_msg: None,
+        index: 0,
+        done: false,
+    }
+}
+
+impl<A, B> Iterator for ReceiversZipEqTimeout<A, B> {
+    type Item = Result<(A, B), RecvZipError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let deadline = Instant::now() + self.timeout;
+
+        // a_msg is only set after b timed out, with `Some(None)` if a was disconnected.
+        let a = match self.a_msg.take() {
+            Some(a) => a,
+            None => match self
+                .a
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            {
+                Ok(a) => Some(a),
+                Err(RecvTimeoutError::Disconnected) => None,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Some(Err(RecvZipError::Timeout(Side::Left)))
+                }
+            },
+        };
+        let b = match self
+            .b
+            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        {
+            Ok(b) => Some(b),
+            Err(RecvTimeoutError::Disconnected) => None,
+            Err(RecvTimeoutError::Timeout) => {
+                self.a_msg = Some(a);
+                return Some(Err(RecvZipError::Timeout(Side::Right)));
+            }
+        };
+
+        let longer = match (a, b) {
+            (Some(a), Some(b)) => {
+                self.index += 1;
+                return Some(Ok((a, b)));
+            }
+            (None, None) => {
+                self.done = true;
+                return None;
+            }
+            (Some(_), None) => Side::Left,
+            (None, Some(_)) => Side::Right,
+        };
+        self.done = true;
+        Some(Err(RecvZipError::Length(LengthMismatch::at(
+            self.index, longer,
+        ))))
+    }
+}
+
+impl<A, B> FusedIterator for ReceiversZipEqTimeout<A, B> {}
@@ -0,0 +1,73 @@
+use core::str::{CharIndices, Chars};
+
+use super::ZipEqEagerCheck;
+
+// `Chars::count` doesn't decode the characters, it counts the bytes that aren't UTF-8
+// continuation bytes several at a time. Checking for ASCII first is cheaper still.
+#[inline]
+fn char_count(s: &str) -> usize {
+    if s.is_ascii() {
+        s.len()
+    } else {
+        s.chars().count()
+    }
+}
+
+#[inline]
+fn check_char_counts(a: &str, b: &str) {
+    if char_count(a) != char_count(b) {
+        super::panic_different_len();
+    }
+}
+
+/// Returns a zipped iterator over the characters of `a` and `b`, after checking that they contain
+/// the same number of characters.
+///
+/// The check only compares the byte lengths when both strings are ASCII, and counts the
+/// characters otherwise. The returned iterator doesn't check anything while it's iterated over.
+/// # Panics
+/// Panics if `a.chars().count() != b.chars().count()`
+///
+/// # Examples
+///
+/// ```
+/// let mut zipped = zip_eq::zip_eq_chars("ab", "çd");
+///
+/// assert_eq!(zipped.next(), Some(('a', 'ç')));
+/// assert_eq!(zipped.next(), Some(('b', 'd')));
+/// assert_eq!(zipped.next(), None);
+/// ```
+pub fn zip_eq_chars<'a, 'b>(a: &'a str, b: &'b str) -> ZipEqEagerCheck<Chars<'a>, Chars<'b>> {
+    check_char_counts(a, b);
+    ZipEqEagerCheck {
+        a: a.chars(),
+        b: b.chars(),
+    }
+}
+
+/// Returns a zipped iterator over the characters of `a` and `b` and their byte positions, after
+/// checking that they contain the same number of characters.
+///
+/// See [`zip_eq_chars`] for details about the check.
+/// # Panics
+/// Panics if `a.chars().count() != b.chars().count()`
+///
+/// # Examples
+///
+/// ```
+/// let mut zipped = zip_eq::zip_eq_char_indices("ab", "çd");
+///
+/// assert_eq!(zipped.next(), Some(((0, 'a'), (0, 'ç'))));
+/// assert_eq!(zipped.next(), Some(((1, 'b'), (2, 'd'))));
+/// assert_eq!(zipped.next(), None);
+/// ```
+pub fn zip_eq_char_indices<'a, 'b>(
+    a: &'a str,
+    b: &'b str,
+) -> ZipEqEagerCheck<CharIndices<'a>, CharIndices<'b>> {
+    check_char_counts(a, b);
+    ZipEqEagerCheck {
+        a: a.char_indices(),
+        b: b.char_indices(),
+    }
+}
@@ -0,0 +1,248 @@
+use core::iter::{FusedIterator, TrustedLen};
+use core::ops::Try;
+use core::slice;
+
+#[cfg(feature = "simd")]
+use core::simd::{Simd, SimdElement};
+
+use super::ZipEqEagerCheck;
+
+/// Iterator over pairs of `N`-element chunks of two slices of the same length.
+///
+/// The elements that don't fit in a full chunk are available through
+/// [`ZipEqChunks::remainder`].
+#[derive(Debug, Clone)]
+pub struct ZipEqChunks<'a, T, U, const N: usize> {
+    chunks: ZipEqEagerCheck<slice::Iter<'a, [T; N]>, slice::Iter<'a, [U; N]>>,
+    remainder: (&'a [T], &'a [U]),
+}
+
+/// Returns an iterator over pairs of `N`-element chunks of `a` and `b`, after checking that the
+/// lengths of the slices are equal.
+/// # Panics
+/// Panics if `a.len() != b.len()` or if `N == 0`.
+///
+/// # Examples
+///
+/// ```
+/// let a = [1, 2, 3, 4, 5];
+/// let b = [6, 7, 8, 9, 10];
+/// let mut zipped = zip_eq::zip_eq_chunks::<2, _, _>(&a, &b);
+///
+/// assert_eq!(zipped.next(), Some((&[1, 2], &[6, 7])));
+/// assert_eq!(zipped.next(), Some((&[3, 4], &[8, 9])));
+/// assert_eq!(zipped.next(), None);
+/// assert_eq!(zipped.remainder(), (&[5][..], &[10][..]));
+/// ```
+pub fn zip_eq_chunks<'a, const N: usize, T, U>(a: &'a [T], b: &'a [U]) -> ZipEqChunks<'a, T, U, N> {
+    if a.len() != b.len() {
+        super::panic_different_len();
+    }
+    let (a, a_rem) = a.as_chunks::<N>();
+    let (b, b_rem) = b.as_chunks::<N>();
+
+    ZipEqChunks {
+        // SAFETY: a and b had the same length, so they have the same number of chunks
+        chunks: ZipEqEagerCheck {
+            a: a.iter(),
+            b: b.iter(),
+        },
+        remainder: (a_rem, b_rem),
+    }
+}
+
+impl<'a, T, U, const N: usize> ZipEqChunks<'a, T, U, N> {
+    /// Returns the elements at the end of the slices that don't fit in a full chunk.
+    /// Both slices have the same length, which is less than `N`.
+    pub fn remainder(&self) -> (&'a [T], &'a [U]) {
+        self.remainder
+    }
+}
+
+impl<'a, T, U, const N: usize> Iterator for ZipEqChunks<'a, T, U, N> {
+    type Item = (&'a [T; N], &'a [U; N]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth(n)
+    }
+
+    #[inline]
+    fn fold<I, F: FnMut(I, Self::Item) -> I>(self, init: I, f: F) -> I {
+        self.chunks.fold(init, f)
+    }
+
+    #[inline]
+    fn try_fold<I, F: FnMut(I, Self::Item) -> R, R>(&mut self, init: I, f: F) -> R
+    where
+        R: Try<Output = I>,
+    {
+        self.chunks.try_fold(init, f)
+    }
+}
+
+impl<T, U, const N: usize> DoubleEndedIterator for ZipEqChunks<'_, T, U, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth_back(n)
+    }
+
+    #[inline]
+    fn rfold<I, F>(self, init: I, f: F) -> I
+    where
+        Self: Sized,
+        F: FnMut(I, Self::Item) -> I,
+    {
+        self.chunks.rfold(init, f)
+    }
+}
+
+impl<T, U, const N: usize> ExactSizeIterator for ZipEqChunks<'_, T, U, N> {
+    fn len(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+unsafe impl<T, U, const N: usize> TrustedLen for ZipEqChunks<'_, T, U, N> {}
+impl<T, U, const N: usize> FusedIterator for ZipEqChunks<'_, T, U, N> {}
+
+/// Iterator over pairs of `LANES`-wide SIMD vectors loaded from two slices of the same length.
+///
+/// The elements that don't fit in a full vector are available through
+/// [`ZipEqSimd::remainder`].
+#[cfg(feature = "simd")]
+#[derive(Debug, Clone)]
+pub struct ZipEqSimd<'a, T, U, const LANES: usize> {
+    chunks: ZipEqChunks<'a, T, U, LANES>,
+}
+
+/// Returns an iterator over pairs of `LANES`-wide SIMD vectors loaded from `a` and `b`, after
+/// checking that the lengths of the slices are equal.
+/// # Panics
+/// Panics if `a.len() != b.len()` or if `LANES == 0`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use std::simd::Simd;
+///
+/// let a = [1.0_f32, 2.0, 3.0, 4.0, 5.0];
+/// let b = [6.0_f32, 7.0, 8.0, 9.0, 10.0];
+/// let zipped = zip_eq::zip_eq_simd::<4, _, _>(&a, &b);
+///
+/// assert_eq!(zipped.remainder(), (&[5.0][..], &[10.0][..]));
+/// let sum = zipped.fold(Simd::splat(0.0), |acc, (a, b)| acc + a * b);
+/// assert_eq!(sum, Simd::from_array([6.0, 14.0, 24.0, 36.0]));
+/// ```
+#[cfg(feature = "simd")]
+pub fn zip_eq_simd<'a, const LANES: usize, T: SimdElement, U: SimdElement>(
+    a: &'a [T],
+    b: &'a [U],
+) -> ZipEqSimd<'a, T, U, LANES> {
+    ZipEqSimd {
+        chunks: zip_eq_chunks(a, b),
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<'a, T: SimdElement, U: SimdElement, const LANES: usize> ZipEqSimd<'a, T, U, LANES> {
+    /// Returns the elements at the end of the slices that don't fit in a full vector.
+    /// Both slices have the same length, which is less than `LANES`.
+    pub fn remainder(&self) -> (&'a [T], &'a [U]) {
+        self.chunks.remainder()
+    }
+}
+
+#[cfg(feature = "simd")]
+#[inline]
+fn load_pair<T: SimdElement, U: SimdElement, const LANES: usize>(
+    (a, b): (&[T; LANES], &[U; LANES]),
+) -> (Simd<T, LANES>, Simd<U, LANES>) {
+    (Simd::from_array(*a), Simd::from_array(*b))
+}
+
+#[cfg(feature = "simd")]
+impl<T: SimdElement, U: SimdElement, const LANES: usize> Iterator for ZipEqSimd<'_, T, U, LANES> {
+    type Item = (Simd<T, LANES>, Simd<U, LANES>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(load_pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth(n).map(load_pair)
+    }
+
+    #[inline]
+    fn fold<I, F: FnMut(I, Self::Item) -> I>(self, init: I, mut f: F) -> I {
+        self.chunks
+            .fold(init, move |init, pair| f(init, load_pair(pair)))
+    }
+
+    #[inline]
+    fn try_fold<I, F: FnMut(I, Self::Item) -> R, R>(&mut self, init: I, mut f: F) -> R
+    where
+        R: Try<Output = I>,
+    {
+        self.chunks
+            .try_fold(init, move |init, pair| f(init, load_pair(pair)))
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<T: SimdElement, U: SimdElement, const LANES: usize> DoubleEndedIterator
+    for ZipEqSimd<'_, T, U, LANES>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back().map(load_pair)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.chunks.nth_back(n).map(load_pair)
+    }
+
+    #[inline]
+    fn rfold<I, F>(self, init: I, mut f: F) -> I
+    where
+        Self: Sized,
+        F: FnMut(I, Self::Item) -> I,
+    {
+        self.chunks
+            .rfold(init, move |init, pair| f(init, load_pair(pair)))
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<T: SimdElement, U: SimdElement, const LANES: usize> ExactSizeIterator
+    for ZipEqSimd<'_, T, U, LANES>
+{
+    fn len(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+#[cfg(feature = "simd")]
+unsafe impl<T: SimdElement, U: SimdElement, const LANES: usize> TrustedLen
+    for ZipEqSimd<'_, T, U, LANES>
+{
+}
+#[cfg(feature = "simd")]
+impl<T: SimdElement, U: SimdElement, const LANES: usize> FusedIterator
+    for ZipEqSimd<'_, T, U, LANES>
+{
+}
@@ -0,0 +1,405 @@
+use core::iter::{FusedIterator, TrustedLen};
+use core::{ptr, slice};
+use std::vec;
+
+use super::eager::unreachable_unchecked;
+use super::{InputsMismatch, LengthMismatch, ZipEqEagerCheck};
+
+fn pair_mismatch(lens: &[usize]) -> Option<LengthMismatch> {
+    (lens[0] != lens[1]).then(|| LengthMismatch::new(lens[0], lens[1]))
+}
+
+/// Implements the methods shared by the column containers, which keep the lengths of their
+/// columns equal.
+macro_rules! column_vec {
+    ($name:ident, $err:ty, $mismatch:path; $($T:ident $field:ident),+) => {
+        impl<$($T),+> $name<$($T),+> {
+            /// Creates an empty container.
+            pub const fn new() -> Self {
+                $name { $($field: Vec::new()),+ }
+            }
+
+            /// Creates an empty container with room for at least `capacity` rows in every
+            /// column.
+            pub fn with_capacity(capacity: usize) -> Self {
+                $name { $($field: Vec::with_capacity(capacity)),+ }
+            }
+
+            /// Creates a container from its columns.
+            /// # Errors
+            /// Returns an error if the columns don't all have the same length.
+            pub fn from_vecs($($field: Vec<$T>),+) -> Result<Self, $err> {
+                match $mismatch(&[$($field.len()),+]) {
+                    Some(err) => Err(err),
+                    None => Ok($name { $($field),+ }),
+                }
+            }
+
+            /// Returns the columns.
+            pub fn into_vecs(self) -> ($(Vec<$T>,)+) {
+                ($(self.$field,)+)
+            }
+
+            /// Returns the number of rows.
+            pub fn len(&self) -> usize {
+                [$(self.$field.len()),+][0]
+            }
+
+            /// Returns `true` if there are no rows.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns the columns as slices of the same length.
+            pub fn as_slices(&self) -> ($(&[$T],)+) {
+                ($(self.$field.as_slice(),)+)
+            }
+
+            /// Returns the columns as mutable slices of the same length.
+            pub fn as_mut_slices(&mut self) -> ($(&mut [$T],)+) {
+                ($(self.$field.as_mut_slice(),)+)
+            }
+
+            /// Returns references to the elements of the row at `index`, or `None` if it is out
+            /// of bounds.
+            pub fn get(&self, index: usize) -> Option<($(&$T,)+)> {
+                if index < self.len() {
+                    Some(($(&self.$field[index],)+))
+                } else {
+                    None
+                }
+            }
+
+            /// Returns mutable references to the elements of the row at `index`, or `None` if it
+            /// is out of bounds.
+            pub fn get_mut(&mut self, index: usize) -> Option<($(&mut $T,)+)> {
+                if index < self.len() {
+                    Some(($(&mut self.$field[index],)+))
+                } else {
+                    None
+                }
+            }
+
+            /// Reserves room for at least `additional` more rows in every column.
+            pub fn reserve(&mut self, additional: usize) {
+                $(self.$field.reserve(additional);)+
+            }
+
+            /// Appends a row.
+            pub fn push(&mut self, ($($field,)+): ($($T,)+)) {
+                // Once the room is reserved, none of the pushes can panic.
+                self.reserve(1);
+                $(self.$field.push($field);)+
+            }
+
+            /// Removes the last row and returns it, or `None` if the container is empty.
+            pub fn pop(&mut self) -> Option<($($T,)+)> {
+                if self.is_empty() {
+                    return None;
+                }
+                // SAFETY: all the columns have at least one element
+                Some(($(unsafe { self.$field.pop().unwrap_unchecked() },)+))
+            }
+
+            /// Inserts a row at `index`, shifting the rows after it.
+            /// # Panics
+            /// Panics if `index > len`
+            pub fn insert(&mut self, index: usize, ($($field,)+): ($($T,)+)) {
+                let len = self.len();
+                assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+                self.reserve(1);
+                $(self.$field.insert(index, $field);)+
+            }
+
+            /// Removes the row at `index` and returns it, shifting the rows after it.
+            /// # Panics
+            /// Panics if `index >= len`
+            pub fn remove(&mut self, index: usize) -> ($($T,)+) {
+                let len = self.len();
+                assert!(index < len, "removal index (is {index}) should be < len (is {len})");
+                ($(self.$field.remove(index),)+)
+            }
+
+            /// Removes the row at `index` and returns it, replacing it with the last row.
+            /// # Panics
+            /// Panics if `index >= len`
+            pub fn swap_remove(&mut self, index: usize) -> ($($T,)+) {
+                let len = self.len();
+                assert!(index < len, "swap_remove index (is {index}) should be < len (is {len})");
+                ($(self.$field.swap_remove(index),)+)
+            }
+
+            /// Keeps the first `len` rows and drops the others.
+            pub fn truncate(&mut self, len: usize) {
+                let old_len = self.len();
+                if len >= old_len {
+                    return;
+                }
+                $(
+                    let $field: *mut [$T] = ptr::slice_from_raw_parts_mut(
+                        self.$field.as_mut_ptr().wrapping_add(len),
+                        old_len - len,
+                    );
+                )+
+                // SAFETY: the tails are initialized, and no longer owned by the columns once
+                // they are shortened. Shortening every column first means that a panicking
+                // destructor can only leak the rest of the tails.
+                unsafe {
+                    $(self.$field.set_len(len);)+
+                    $(ptr::drop_in_place($field);)+
+                }
+            }
+
+            /// Removes all the rows.
+            pub fn clear(&mut self) {
+                self.truncate(0);
+            }
+        }
+
+        impl<$($T),+> Default for $name<$($T),+> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<$($T),+> Extend<($($T,)+)> for $name<$($T),+> {
+            fn extend<I: IntoIterator<Item = ($($T,)+)>>(&mut self, iter: I) {
+                let iter = iter.into_iter();
+                self.reserve(iter.size_hint().0);
+                iter.for_each(|row| self.push(row));
+            }
+        }
+
+        impl<$($T),+> FromIterator<($($T,)+)> for $name<$($T),+> {
+            fn from_iter<I: IntoIterator<Item = ($($T,)+)>>(iter: I) -> Self {
+                let mut columns = Self::new();
+                columns.extend(iter);
+                columns
+            }
+        }
+    };
+}
+
+/// Two vectors whose lengths are always equal, storing pairs as a struct of arrays.
+///
+/// Iterating over the pairs doesn't check the lengths, since they are known to be equal.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::PairVec;
+///
+/// let mut points = PairVec::from_vecs(vec![1, 2], vec![3.0, 4.0]).unwrap();
+/// points.push((5, 6.0));
+///
+/// assert_eq!(points.len(), 3);
+/// assert_eq!(points.as_slices(), ([1, 2, 5].as_slice(), [3.0, 4.0, 6.0].as_slice()));
+/// for (x, y) in points.iter_mut() {
+///     *y *= *x as f64;
+/// }
+/// assert_eq!(points.remove(1), (2, 8.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PairVec<A, B> {
+    a: Vec<A>,
+    b: Vec<B>,
+}
+
+column_vec!(PairVec, LengthMismatch, pair_mismatch; A a, B b);
+
+impl<A, B> PairVec<A, B> {
+    /// Returns an iterator over references to the pairs.
+    pub fn iter(&self) -> ZipEqEagerCheck<slice::Iter<'_, A>, slice::Iter<'_, B>> {
+        // SAFETY: the columns have the same length
+        ZipEqEagerCheck {
+            a: self.a.iter(),
+            b: self.b.iter(),
+        }
+    }
+
+    /// Returns an iterator over mutable references to the pairs.
+    pub fn iter_mut(&mut self) -> ZipEqEagerCheck<slice::IterMut<'_, A>, slice::IterMut<'_, B>> {
+        // SAFETY: the columns have the same length
+        ZipEqEagerCheck {
+            a: self.a.iter_mut(),
+            b: self.b.iter_mut(),
+        }
+    }
+}
+
+impl<A, B> IntoIterator for PairVec<A, B> {
+    type Item = (A, B);
+    type IntoIter = ZipEqEagerCheck<vec::IntoIter<A>, vec::IntoIter<B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // SAFETY: the columns have the same length
+        ZipEqEagerCheck {
+            a: self.a.into_iter(),
+            b: self.b.into_iter(),
+        }
+    }
+}
+
+impl<'a, A, B> IntoIterator for &'a PairVec<A, B> {
+    type Item = (&'a A, &'a B);
+    type IntoIter = ZipEqEagerCheck<slice::Iter<'a, A>, slice::Iter<'a, B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A, B> IntoIterator for &'a mut PairVec<A, B> {
+    type Item = (&'a mut A, &'a mut B);
+    type IntoIter = ZipEqEagerCheck<slice::IterMut<'a, A>, slice::IterMut<'a, B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the rows of a column container, yielding tuples built from the items of
+/// iterators that have the same length.
+#[derive(Debug, Clone)]
+pub struct ZipColumns<T> {
+    iters: T,
+}
+
+macro_rules! zip_columns {
+    ($First:ident $first:ident, $($I:ident $iter:ident),+) => {
+        // SAFETY: all the iterators have the same length
+        impl<$First: Iterator, $($I: Iterator),+> Iterator for ZipColumns<($First, $($I,)+)> {
+            type Item = ($First::Item, $($I::Item,)+);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($first, $($iter,)+) = &mut self.iters;
+                let first = $first.next()?;
+                Some((first, $(match $iter.next() {
+                    Some(item) => item,
+                    None => unsafe { unreachable_unchecked() },
+                },)+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iters.0.size_hint()
+            }
+        }
+
+        impl<$First, $($I),+> DoubleEndedIterator for ZipColumns<($First, $($I,)+)>
+        where
+            $First: DoubleEndedIterator,
+            $($I: DoubleEndedIterator,)+
+        {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let ($first, $($iter,)+) = &mut self.iters;
+                let first = $first.next_back()?;
+                Some((first, $(match $iter.next_back() {
+                    Some(item) => item,
+                    None => unsafe { unreachable_unchecked() },
+                },)+))
+            }
+        }
+
+        impl<$First: ExactSizeIterator, $($I: Iterator),+> ExactSizeIterator
+            for ZipColumns<($First, $($I,)+)>
+        {
+            fn len(&self) -> usize {
+                self.iters.0.len()
+            }
+        }
+
+        unsafe impl<$First: TrustedLen, $($I: Iterator),+> TrustedLen
+            for ZipColumns<($First, $($I,)+)>
+        {
+        }
+
+        impl<$First: FusedIterator, $($I: Iterator),+> FusedIterator
+            for ZipColumns<($First, $($I,)+)>
+        {
+        }
+    };
+}
+
+zip_columns!(A a, B b, C c);
+zip_columns!(A a, B b, C c, D d);
+zip_columns!(A a, B b, C c, D d, E e);
+zip_columns!(A a, B b, C c, D d, E e, F f);
+
+macro_rules! column_vecs {
+    ($($(#[$attr:meta])* $name:ident: $($T:ident $field:ident),+;)+) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name<$($T),+> {
+            $($field: Vec<$T>,)+
+        }
+
+        column_vec!($name, InputsMismatch, InputsMismatch::from_lens; $($T $field),+);
+
+        impl<$($T),+> $name<$($T),+> {
+            /// Returns an iterator over references to the rows.
+            pub fn iter(&self) -> ZipColumns<($(slice::Iter<'_, $T>,)+)> {
+                // SAFETY: the columns have the same length
+                ZipColumns { iters: ($(self.$field.iter(),)+) }
+            }
+
+            /// Returns an iterator over mutable references to the rows.
+            pub fn iter_mut(&mut self) -> ZipColumns<($(slice::IterMut<'_, $T>,)+)> {
+                // SAFETY: the columns have the same length
+                ZipColumns { iters: ($(self.$field.iter_mut(),)+) }
+            }
+        }
+
+        impl<$($T),+> IntoIterator for $name<$($T),+> {
+            type Item = ($($T,)+);
+            type IntoIter = ZipColumns<($(vec::IntoIter<$T>,)+)>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                // SAFETY: the columns have the same length
+                ZipColumns { iters: ($(self.$field.into_iter(),)+) }
+            }
+        }
+
+        impl<'a, $($T),+> IntoIterator for &'a $name<$($T),+> {
+            type Item = ($(&'a $T,)+);
+            type IntoIter = ZipColumns<($(slice::Iter<'a, $T>,)+)>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<'a, $($T),+> IntoIterator for &'a mut $name<$($T),+> {
+            type Item = ($(&'a mut $T,)+);
+            type IntoIter = ZipColumns<($(slice::IterMut<'a, $T>,)+)>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
+        }
+    )+};
+}
+
+column_vecs! {
+    /// Three vectors whose lengths are always equal, like [`PairVec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::ColumnVec3;
+    ///
+    /// let mut rows: ColumnVec3<_, _, _> = [(1, 'a', true), (2, 'b', false)].into_iter().collect();
+    /// rows.insert(0, (0, 'z', true));
+    ///
+    /// assert_eq!(rows.iter().next(), Some((&0, &'z', &true)));
+    /// assert!(ColumnVec3::from_vecs(vec![1], vec!['b'], Vec::<bool>::new()).is_err());
+    /// ```
+    ColumnVec3: A a, B b, C c;
+    /// Four vectors whose lengths are always equal, like [`PairVec`].
+    ColumnVec4: A a, B b, C c, D d;
+    /// Five vectors whose lengths are always equal, like [`PairVec`].
+    ColumnVec5: A a, B b, C c, D d, E e;
+    /// Six vectors whose lengths are always equal, like [`PairVec`].
+    ColumnVec6: A a, B b, C c, D d, E e, F f;
+}
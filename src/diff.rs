@@ -0,0 +1,149 @@
+use core::fmt;
+
+use super::{Side, ZipEq};
+
+/// The first difference between two sequences, returned by [`first_difference`] and
+/// [`first_difference_by`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difference<T, U> {
+    /// The elements at `index` are different.
+    ValueMismatch {
+        /// Index of the elements.
+        index: usize,
+        /// Element of the left input.
+        left: T,
+        /// Element of the right input.
+        right: U,
+    },
+    /// One of the inputs ended after `index` elements, which were all equal, while the other one
+    /// had `extra` more elements.
+    LengthMismatch {
+        /// Length of the shorter input.
+        index: usize,
+        /// Side of the longer input.
+        longer_side: Side,
+        /// Number of elements of the longer input that have no counterpart.
+        extra: usize,
+    },
+}
+
+impl<T, U> Difference<T, U> {
+    /// Returns the index of the first difference, which is the length of the shorter input for
+    /// a length mismatch.
+    pub fn index(&self) -> usize {
+        match *self {
+            Difference::ValueMismatch { index, .. } | Difference::LengthMismatch { index, .. } => {
+                index
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> fmt::Display for Difference<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::ValueMismatch { index, left, right } => {
+                write!(
+                    f,
+                    "elements at index {index} differ: left is {left:?} but right is {right:?}"
+                )
+            }
+            Difference::LengthMismatch {
+                index,
+                longer_side,
+                extra,
+            } => write!(
+                f,
+                "{} input ended after {index} elements but {longer_side} input has {extra} more",
+                longer_side.other(),
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> std::error::Error for Difference<T, U> {}
+
+/// Returns the first difference between the elements of `a` and `b`, or `None` if they are
+/// equal and have the same length.
+///
+/// Unlike [`Iterator::eq`], the result tells where the inputs diverged. On a length mismatch,
+/// the rest of the longer input is consumed to count its extra elements.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::{first_difference, Difference, Side};
+///
+/// assert_eq!(first_difference([1, 2, 3], [1, 2, 3]), None);
+/// assert_eq!(
+///     first_difference([1, 2, 3], [1, 5, 3]),
+///     Some(Difference::ValueMismatch { index: 1, left: 2, right: 5 })
+/// );
+///
+/// let diff = first_difference([1, 2], [1, 2, 3, 4]).unwrap();
+/// assert_eq!(
+///     diff,
+///     Difference::LengthMismatch { index: 2, longer_side: Side::Right, extra: 2 }
+/// );
+/// assert_eq!(diff.to_string(), "left input ended after 2 elements but right input has 2 more");
+/// ```
+pub fn first_difference<A, B>(a: A, b: B) -> Option<Difference<A::Item, B::Item>>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+    A::Item: PartialEq<B::Item>,
+{
+    first_difference_by(a, b, |a, b| a == b)
+}
+
+/// Returns the first difference between the elements of `a` and `b`, using `eq` to compare
+/// them, or `None` if they are equal and have the same length.
+///
+/// On a length mismatch, the rest of the longer input is consumed to count its extra elements.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::{first_difference_by, Difference};
+///
+/// let expected = [1.0, 2.0, 3.0];
+/// let actual = [1.0, 2.0001, 3.5];
+/// let close = |a: &f64, b: &f64| (a - b).abs() < 1e-3;
+///
+/// assert_eq!(
+///     first_difference_by(expected, actual, close),
+///     Some(Difference::ValueMismatch { index: 2, left: 3.0, right: 3.5 })
+/// );
+/// ```
+pub fn first_difference_by<A, B, F>(a: A, b: B, mut eq: F) -> Option<Difference<A::Item, B::Item>>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+    F: FnMut(&A::Item, &B::Item) -> bool,
+{
+    let mut zipped = a.zip_eq_lazy(b);
+    let mut index = 0;
+    loop {
+        match zipped.try_next() {
+            Ok(Some((left, right))) => {
+                if !eq(&left, &right) {
+                    return Some(Difference::ValueMismatch { index, left, right });
+                }
+                index += 1;
+            }
+            Ok(None) => return None,
+            Err(longer_side) => {
+                // The element that revealed the mismatch was already taken.
+                let rest = match longer_side {
+                    Side::Left => zipped.a.count(),
+                    Side::Right => zipped.b.count(),
+                };
+                return Some(Difference::LengthMismatch {
+                    index,
+                    longer_side,
+                    extra: rest + 1,
+                });
+            }
+        }
+    }
+}
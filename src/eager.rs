@@ -1,8 +1,25 @@
-use core::iter::{FusedIterator, TrustedLen};
+use core::iter::{FusedIterator, InPlaceIterable, SourceIter, TrustedLen};
+use core::num::NonZero;
 use core::ops::Try;
 
 /// Iterator that zips two iterators, checking that they have the same length during
 /// construction.
+///
+/// When the left input is a `vec::IntoIter`, possibly behind other adapters, collecting into a
+/// `Vec` reuses its allocation when the layouts allow it, as with [`Iterator::zip`].
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ZipEq;
+///
+/// let a = vec![1_u32, 2, 3];
+/// let ptr = a.as_ptr();
+/// let sums: Vec<u32> = a.zip_eq_eager(vec![4, 5, 6]).map(|(a, b)| a + b).collect();
+///
+/// assert_eq!(sums, [5, 7, 9]);
+/// assert_eq!(sums.as_ptr(), ptr);
+/// ```
 #[derive(Debug, Clone)]
 pub struct ZipEqEagerCheck<A, B> {
     pub(crate) a: A,
@@ -10,7 +27,7 @@ pub struct ZipEqEagerCheck<A, B> {
 }
 
 #[inline]
-unsafe fn unreachable_unchecked() -> ! {
+pub(crate) unsafe fn unreachable_unchecked() -> ! {
     #[cfg(not(debug_assertions))]
     unsafe {
         core::hint::unreachable_unchecked()
@@ -148,3 +165,20 @@ impl<A: ExactSizeIterator, B: ExactSizeIterator> ExactSizeIterator for ZipEqEage
 
 unsafe impl<A: TrustedLen, B: Iterator> TrustedLen for ZipEqEagerCheck<A, B> {}
 impl<A: FusedIterator, B: Iterator> FusedIterator for ZipEqEagerCheck<A, B> {}
+
+// Like `core::iter::Zip`, the in-place collection forwards to the left input: every item is built
+// from exactly one item of `a`, which is read before the item is written back to its buffer.
+unsafe impl<A: SourceIter, B> SourceIter for ZipEqEagerCheck<A, B> {
+    type Source = A::Source;
+
+    #[inline]
+    unsafe fn as_inner(&mut self) -> &mut A::Source {
+        // SAFETY: forwarded to the left input, with the same requirements
+        unsafe { SourceIter::as_inner(&mut self.a) }
+    }
+}
+
+unsafe impl<A: InPlaceIterable, B> InPlaceIterable for ZipEqEagerCheck<A, B> {
+    const EXPAND_BY: Option<NonZero<usize>> = A::EXPAND_BY;
+    const MERGE_BY: Option<NonZero<usize>> = A::MERGE_BY;
+}
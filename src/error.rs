@@ -0,0 +1,569 @@
+use core::fmt;
+
+/// One of the two zipped inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The first input, or `self` in the `zip_eq_*` methods.
+    Left,
+    /// The second input.
+    Right,
+}
+
+impl Side {
+    /// Returns the other side.
+    pub fn other(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Side::Left => "left",
+            Side::Right => "right",
+        })
+    }
+}
+
+/// Error reported when two zipped inputs don't have the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    index: usize,
+    longer: Side,
+    longer_len: Option<usize>,
+    labels: [Option<&'static str>; 2],
+}
+
+impl LengthMismatch {
+    /// Returns a mismatch between two inputs whose lengths are known, as found by an eager check.
+    /// # Panics
+    /// Panics if `left_len == right_len`
+    pub fn new(left_len: usize, right_len: usize) -> Self {
+        assert_ne!(left_len, right_len, "LengthMismatch: the lengths are equal");
+        let (longer, index, longer_len) = if left_len > right_len {
+            (Side::Left, right_len, left_len)
+        } else {
+            (Side::Right, left_len, right_len)
+        };
+        LengthMismatch {
+            index,
+            longer,
+            longer_len: Some(longer_len),
+            labels: [None; 2],
+        }
+    }
+
+    /// Returns a mismatch found during iteration, when the shorter input ended after `index`
+    /// elements while the input on the `longer` side still had some left.
+    pub fn at(index: usize, longer: Side) -> Self {
+        LengthMismatch {
+            index,
+            longer,
+            longer_len: None,
+            labels: [None; 2],
+        }
+    }
+
+    /// Names the inputs, as by [`ZipEq::label`](super::ZipEq::label). The error messages use
+    /// the labels instead of "left input" and "right input".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::LengthMismatch;
+    ///
+    /// let err = LengthMismatch::new(10, 9).with_labels(Some("weights"), Some("bias"));
+    /// assert_eq!(err.to_string(), "weights has 10 elements but bias has 9");
+    /// ```
+    pub fn with_labels(self, left: Option<&'static str>, right: Option<&'static str>) -> Self {
+        LengthMismatch {
+            labels: [left, right],
+            ..self
+        }
+    }
+
+    /// Returns the label of the left input, if it has one.
+    pub fn left_label(&self) -> Option<&'static str> {
+        self.labels[0]
+    }
+
+    /// Returns the label of the right input, if it has one.
+    pub fn right_label(&self) -> Option<&'static str> {
+        self.labels[1]
+    }
+
+    /// Returns the length of the shorter input, which is also the index of the first element of
+    /// the longer input that has no counterpart.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the side of the longer input.
+    pub fn longer_side(&self) -> Side {
+        self.longer
+    }
+
+    /// Returns the length of the left input, if it is known.
+    pub fn left_len(&self) -> Option<usize> {
+        self.len(Side::Left)
+    }
+
+    /// Returns the length of the right input, if it is known.
+    pub fn right_len(&self) -> Option<usize> {
+        self.len(Side::Right)
+    }
+
+    fn len(&self, side: Side) -> Option<usize> {
+        if side == self.longer {
+            self.longer_len
+        } else {
+            Some(self.index)
+        }
+    }
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let left = InputName(self.labels[0], Side::Left);
+        let right = InputName(self.labels[1], Side::Right);
+        match (self.left_len(), self.right_len()) {
+            (Some(left_len), Some(right_len)) => write!(
+                f,
+                "{left} has {left_len} elements but {right} has {right_len}"
+            ),
+            _ => {
+                let (shorter, longer) = match self.longer {
+                    Side::Left => (right, left),
+                    Side::Right => (left, right),
+                };
+                write!(
+                    f,
+                    "{shorter} ended after {} elements but {longer} has more",
+                    self.index,
+                )
+            }
+        }
+    }
+}
+
+/// Name of an input in error messages: its label, or its side.
+#[derive(Clone, Copy)]
+pub(crate) struct InputName(pub(crate) Option<&'static str>, pub(crate) Side);
+
+impl fmt::Display for InputName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(label) => f.write_str(label),
+            None => write!(f, "{} input", self.1),
+        }
+    }
+}
+
+impl std::error::Error for LengthMismatch {}
+
+/// Error reported when one of several zipped inputs ends before the others, as by
+/// [`ZipEqAll::try_next`](super::ZipEqAll::try_next).
+///
+/// Inputs are identified by their position among the zipped inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputsMismatch {
+    index: usize,
+    ended: usize,
+    continued: usize,
+}
+
+impl InputsMismatch {
+    /// Returns a mismatch where input `ended` ended after `index` elements while input
+    /// `continued` still had some left.
+    /// # Panics
+    /// Panics if `ended == continued`
+    pub fn new(index: usize, ended: usize, continued: usize) -> Self {
+        assert_ne!(ended, continued, "InputsMismatch: the inputs are the same");
+        InputsMismatch {
+            index,
+            ended,
+            continued,
+        }
+    }
+
+    /// Returns the mismatch between inputs of lengths `lens`, or `None` if they are all equal.
+    ///
+    /// The mismatch is between the first of the shortest inputs and the first input that is
+    /// longer than it.
+    pub fn from_lens(lens: &[usize]) -> Option<Self> {
+        let shortest = lens.iter().copied().min()?;
+        let continued = lens.iter().position(|&len| len > shortest)?;
+        let ended = lens.iter().position(|&len| len == shortest)?;
+        Some(InputsMismatch::new(shortest, ended, continued))
+    }
+
+    /// Returns the length of the shorter inputs, which is also the index of the first element of
+    /// the longer inputs that has no counterpart.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the position of the first input that ended.
+    pub fn ended(&self) -> usize {
+        self.ended
+    }
+
+    /// Returns the position of the first input that still had elements.
+    pub fn continued(&self) -> usize {
+        self.continued
+    }
+}
+
+impl fmt::Display for InputsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "input {} ended after {} elements but input {} has more",
+            self.ended, self.index, self.continued,
+        )
+    }
+}
+
+impl std::error::Error for InputsMismatch {}
+
+/// Error reported when two inputs zipped by [`zip_eq_by_key`](super::zip_eq_by_key) have
+/// elements with different keys at the same position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyMismatch<K> {
+    index: usize,
+    left_key: K,
+    right_key: K,
+    labels: [Option<&'static str>; 2],
+}
+
+impl<K> KeyMismatch<K> {
+    /// Returns a mismatch between the keys of the elements at `index`.
+    pub fn new(index: usize, left_key: K, right_key: K) -> Self {
+        KeyMismatch {
+            index,
+            left_key,
+            right_key,
+            labels: [None; 2],
+        }
+    }
+
+    /// Names the inputs, as by [`ZipEq::label`](super::ZipEq::label).
+    pub fn with_labels(self, left: Option<&'static str>, right: Option<&'static str>) -> Self {
+        KeyMismatch {
+            labels: [left, right],
+            ..self
+        }
+    }
+
+    /// Returns the index of the elements whose keys differ.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the key of the element of the left input.
+    pub fn left_key(&self) -> &K {
+        &self.left_key
+    }
+
+    /// Returns the key of the element of the right input.
+    pub fn right_key(&self) -> &K {
+        &self.right_key
+    }
+
+    /// Returns the keys of the left and right elements.
+    pub fn into_keys(self) -> (K, K) {
+        (self.left_key, self.right_key)
+    }
+}
+
+impl<K: fmt::Debug> fmt::Display for KeyMismatch<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "keys at index {} differ: {} has {:?} but {} has {:?}",
+            self.index,
+            InputName(self.labels[0], Side::Left),
+            self.left_key,
+            InputName(self.labels[1], Side::Right),
+            self.right_key,
+        )
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for KeyMismatch<K> {}
+
+/// Error yielded by [`ZipEqByKey::try_next`](super::ZipEqByKey::try_next), either because the
+/// inputs don't have the same length or because the keys of two elements differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyZipError<K> {
+    /// One of the inputs ended before the other.
+    Length(LengthMismatch),
+    /// The elements at the same index have different keys.
+    Key(KeyMismatch<K>),
+}
+
+impl<K: fmt::Debug> fmt::Display for KeyZipError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyZipError::Length(err) => err.fmt(f),
+            KeyZipError::Key(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<K: fmt::Debug + 'static> std::error::Error for KeyZipError<K> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeyZipError::Length(err) => Some(err),
+            KeyZipError::Key(err) => Some(err),
+        }
+    }
+}
+
+/// Error reported when a key is present in only one of two maps zipped by
+/// [`zip_eq_maps`](super::zip_eq_maps) or [`zip_eq_hash_maps`](super::zip_eq_hash_maps).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnmatchedKey<K> {
+    key: K,
+    present_in: Side,
+    labels: [Option<&'static str>; 2],
+}
+
+impl<K> UnmatchedKey<K> {
+    /// Returns an error for `key`, which is only in the input on the `present_in` side.
+    pub fn new(key: K, present_in: Side) -> Self {
+        UnmatchedKey {
+            key,
+            present_in,
+            labels: [None; 2],
+        }
+    }
+
+    /// Names the inputs, as by [`ZipEq::label`](super::ZipEq::label).
+    pub fn with_labels(self, left: Option<&'static str>, right: Option<&'static str>) -> Self {
+        UnmatchedKey {
+            labels: [left, right],
+            ..self
+        }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key, consuming the error.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Returns the side of the input that has the key.
+    pub fn present_in(&self) -> Side {
+        self.present_in
+    }
+}
+
+impl<K: fmt::Debug> fmt::Display for UnmatchedKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |side| match side {
+            Side::Left => InputName(self.labels[0], side),
+            Side::Right => InputName(self.labels[1], side),
+        };
+        write!(
+            f,
+            "key {:?} is in {} but not in {}",
+            self.key,
+            name(self.present_in),
+            name(self.present_in.other()),
+        )
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for UnmatchedKey<K> {}
+
+/// Error reported by [`collect_map_eq`](super::collect_map_eq) when a key appears twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DuplicateKey<K> {
+    key: K,
+    first_index: usize,
+    index: usize,
+}
+
+impl<K> DuplicateKey<K> {
+    /// Returns an error for `key`, found at `first_index` and again at `index`.
+    pub fn new(key: K, first_index: usize, index: usize) -> Self {
+        DuplicateKey {
+            key,
+            first_index,
+            index,
+        }
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key, consuming the error.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Returns the index of the first occurrence of the key.
+    pub fn first_index(&self) -> usize {
+        self.first_index
+    }
+
+    /// Returns the index of the second occurrence of the key.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<K: fmt::Debug> fmt::Display for DuplicateKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key {:?} appears at index {} and at index {}",
+            self.key, self.first_index, self.index,
+        )
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for DuplicateKey<K> {}
+
+/// Error returned by [`collect_map_eq`](super::collect_map_eq).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapEqError<K> {
+    /// The keys and values don't have the same length.
+    Length(LengthMismatch),
+    /// A key appears twice.
+    Duplicate(DuplicateKey<K>),
+}
+
+impl<K: fmt::Debug> fmt::Display for MapEqError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapEqError::Length(err) => err.fmt(f),
+            MapEqError::Duplicate(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<K: fmt::Debug + 'static> std::error::Error for MapEqError<K> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MapEqError::Length(err) => Some(err),
+            MapEqError::Duplicate(err) => Some(err),
+        }
+    }
+}
+
+/// Error reported by [`check_shape`](super::check_shape) and
+/// [`zip_eq_nested`](super::zip_eq_nested) when two nested containers don't have the same shape.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NestedMismatch {
+    path: Vec<usize>,
+    left_len: usize,
+    right_len: usize,
+}
+
+impl NestedMismatch {
+    /// Returns a mismatch between containers of lengths `left_len` and `right_len`, found by
+    /// following the indices of `path` from the outermost containers.
+    pub fn new(path: Vec<usize>, left_len: usize, right_len: usize) -> Self {
+        NestedMismatch {
+            path,
+            left_len,
+            right_len,
+        }
+    }
+
+    /// Returns the indices leading to the containers whose lengths differ, starting with the
+    /// index of the row. It is empty if the outermost containers have different lengths.
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// Returns the length of the left container.
+    pub fn left_len(&self) -> usize {
+        self.left_len
+    }
+
+    /// Returns the length of the right container.
+    pub fn right_len(&self) -> usize {
+        self.right_len
+    }
+
+    pub(crate) fn in_row(mut self, index: usize) -> Self {
+        self.path.insert(0, index);
+        self
+    }
+}
+
+impl fmt::Display for NestedMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((row, rest)) = self.path.split_first() else {
+            return write!(f, "{} vs {} rows", self.left_len, self.right_len);
+        };
+        write!(f, "row {row}")?;
+        for index in rest {
+            write!(f, ", element {index}")?;
+        }
+        write!(f, ": {} vs {} elements", self.left_len, self.right_len)
+    }
+}
+
+impl std::error::Error for NestedMismatch {}
+
+/// Error reported by [`transpose_eq`](super::transpose_eq) and
+/// [`transpose_eq_lazy`](super::transpose_eq_lazy) when a row doesn't have the same length as
+/// the first one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RaggedError {
+    row: usize,
+    row_len: usize,
+    expected_len: usize,
+}
+
+impl RaggedError {
+    /// Returns an error for row `row`, of length `row_len`, when the first row has
+    /// `expected_len` elements.
+    pub fn new(row: usize, row_len: usize, expected_len: usize) -> Self {
+        RaggedError {
+            row,
+            row_len,
+            expected_len,
+        }
+    }
+
+    /// Returns the index of the row.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the length of the row.
+    pub fn row_len(&self) -> usize {
+        self.row_len
+    }
+
+    /// Returns the length of the first row.
+    pub fn expected_len(&self) -> usize {
+        self.expected_len
+    }
+}
+
+impl fmt::Display for RaggedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} elements but row 0 has {}",
+            self.row, self.row_len, self.expected_len,
+        )
+    }
+}
+
+impl std::error::Error for RaggedError {}
@@ -0,0 +1,224 @@
+use core::convert::Infallible;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{ControlFlow, Try};
+
+use super::lazy::Orphan;
+use super::{LengthMismatch, Side, ZipEqLazyCheck};
+
+/// Error yielded by [`TryZipEq`], either from one of the inputs or because the inputs don't have
+/// the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZipError<E1, E2> {
+    /// The left input yielded an error.
+    Left(E1),
+    /// The right input yielded an error.
+    Right(E2),
+    /// One of the inputs ended before the other.
+    Length(LengthMismatch),
+}
+
+impl<E1, E2> ZipError<E1, E2> {
+    /// Returns the side of the input that yielded the error, or `None` for a length mismatch.
+    pub fn side(&self) -> Option<Side> {
+        match self {
+            ZipError::Left(_) => Some(Side::Left),
+            ZipError::Right(_) => Some(Side::Right),
+            ZipError::Length(_) => None,
+        }
+    }
+}
+
+impl<E1: fmt::Display, E2: fmt::Display> fmt::Display for ZipError<E1, E2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZipError::Left(err) => write!(f, "left input: {err}"),
+            ZipError::Right(err) => write!(f, "right input: {err}"),
+            ZipError::Length(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E1, E2> std::error::Error for ZipError<E1, E2>
+where
+    E1: std::error::Error + 'static,
+    E2: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZipError::Left(err) => Some(err),
+            ZipError::Right(err) => Some(err),
+            ZipError::Length(err) => Some(err),
+        }
+    }
+}
+
+/// Iterator that zips two iterators of `Result`s, created by [`try_zip_eq`].
+///
+/// It yields `Ok` pairs as long as both inputs yield `Ok` values, and stops after yielding the
+/// first error, whether it comes from one of the inputs or from a length mismatch.
+#[derive(Debug, Clone)]
+pub struct TryZipEq<A, B> {
+    zipped: ZipEqLazyCheck<A, B>,
+    index: usize,
+    done: bool,
+}
+
+/// Returns an iterator that zips two iterators of `Result`s, checking that they have the same
+/// length during iteration.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::{try_zip_eq, LengthMismatch, Side, ZipError};
+///
+/// let a: [Result<_, ()>; 3] = [Ok(1), Ok(2), Ok(3)];
+/// let b: [Result<_, ()>; 2] = [Ok(4), Ok(5)];
+/// let mut zipped = try_zip_eq(a, b);
+///
+/// assert_eq!(zipped.next(), Some(Ok((1, 4))));
+/// assert_eq!(zipped.next(), Some(Ok((2, 5))));
+/// assert_eq!(
+///     zipped.next(),
+///     Some(Err(ZipError::Length(LengthMismatch::at(2, Side::Left))))
+/// );
+/// assert_eq!(zipped.next(), None);
+/// ```
+pub fn try_zip_eq<A, B, T, U, E1, E2>(a: A, b: B) -> TryZipEq<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator<Item = Result<T, E1>>,
+    B: IntoIterator<Item = Result<U, E2>>,
+{
+    TryZipEq {
+        zipped: ZipEqLazyCheck {
+            a: a.into_iter(),
+            b: b.into_iter(),
+        },
+        index: 0,
+        done: false,
+    }
+}
+
+/// Pairs up the next items of both sides, or returns the error ending the iteration.
+#[inline]
+fn pair<T, U, E1, E2>(
+    index: usize,
+    a: Option<Result<T, E1>>,
+    b: Option<Result<U, E2>>,
+    [left, right]: [Option<&'static str>; 2],
+) -> Option<Result<(T, U), ZipError<E1, E2>>> {
+    let longer = match (a, b) {
+        (Some(Ok(a)), Some(Ok(b))) => return Some(Ok((a, b))),
+        (None, None) => return None,
+        (Some(Err(err)), _) => return Some(Err(ZipError::Left(err))),
+        (_, Some(Err(err))) => return Some(Err(ZipError::Right(err))),
+        (Some(Ok(_)), None) => Side::Left,
+        (None, Some(Ok(_))) => Side::Right,
+    };
+    Some(Err(ZipError::Length(
+        LengthMismatch::at(index, longer).with_labels(left, right),
+    )))
+}
+
+impl<A, B, T, U, E1, E2> Iterator for TryZipEq<A, B>
+where
+    A: Iterator<Item = Result<T, E1>>,
+    B: Iterator<Item = Result<U, E2>>,
+{
+    type Item = Result<(T, U), ZipError<E1, E2>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let labels = self.zipped.labels();
+        let item = pair(
+            self.index,
+            self.zipped.a.next(),
+            self.zipped.b.next(),
+            labels,
+        );
+        match item {
+            Some(Ok(_)) => self.index += 1,
+            _ => self.done = true,
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (a_lo, a_hi) = self.zipped.a.size_hint();
+        let (b_lo, b_hi) = self.zipped.b.size_hint();
+        // Any error ends the iteration, and a length mismatch is one more item.
+        (
+            usize::from(a_lo > 0 || b_lo > 0),
+            match (a_hi, b_hi) {
+                (Some(a), Some(b)) => a.min(b).checked_add(1),
+                (Some(a), None) => a.checked_add(1),
+                (None, Some(b)) => b.checked_add(1),
+                (None, None) => None,
+            },
+        )
+    }
+
+    #[inline]
+    fn try_fold<I, F: FnMut(I, Self::Item) -> R, R>(&mut self, init: I, mut f: F) -> R
+    where
+        R: Try<Output = I>,
+    {
+        if self.done {
+            return R::from_output(init);
+        }
+        let labels = self.zipped.labels();
+        let TryZipEq {
+            zipped,
+            index,
+            done,
+        } = self;
+
+        // Passes the next item to `f`, and breaks after an error was passed to it.
+        let flow = zipped.try_fold_checked(init, |acc, next| {
+            let (a, b) = match next {
+                Ok(pair) => (Some(pair.0), Some(pair.1)),
+                Err(Orphan::Left(a)) => (Some(a), None),
+                Err(Orphan::Right(b)) => (None, Some(b)),
+            };
+            let Some(item) = pair(*index, a, b, labels) else {
+                unreachable!("`try_fold_checked` passes at least one element")
+            };
+            let stop = item.is_err();
+            if stop {
+                *done = true;
+            } else {
+                *index += 1;
+            }
+            match f(acc, item).branch() {
+                ControlFlow::Continue(acc) if stop => ControlFlow::Break(R::from_output(acc)),
+                ControlFlow::Continue(acc) => ControlFlow::Continue(acc),
+                ControlFlow::Break(residual) => ControlFlow::Break(R::from_residual(residual)),
+            }
+        });
+        match flow {
+            ControlFlow::Continue(acc) => {
+                *done = true;
+                R::from_output(acc)
+            }
+            ControlFlow::Break(r) => r,
+        }
+    }
+
+    #[inline]
+    fn fold<I, F: FnMut(I, Self::Item) -> I>(mut self, init: I, mut f: F) -> I {
+        let Ok(acc) = self.try_fold(init, |acc, item| Ok::<_, Infallible>(f(acc, item)));
+        acc
+    }
+}
+
+impl<A, B, T, U, E1, E2> FusedIterator for TryZipEq<A, B>
+where
+    A: Iterator<Item = Result<T, E1>>,
+    B: Iterator<Item = Result<U, E2>>,
+{
+}
@@ -0,0 +1,145 @@
+/// Loops over several inputs of the same length, binding one element of each to a flat list of
+/// patterns.
+///
+/// `for_zip_eq!((o, a, b) in (out, a, b) { ... })` is equivalent to
+/// `for ((o, a), b) in out.zip_eq_eager(a).zip_eq_eager(b) { ... }`, where each input is turned
+/// into an iterator with [`IntoIterator`]. The inputs are zipped with
+/// [`ZipEq::zip_eq_eager`](crate::ZipEq::zip_eq_eager) when both sides are [`TrustedLen`] and
+/// [`ExactSizeIterator`]s, which checks their lengths before the first iteration, and with
+/// [`ZipEq::zip_eq_lazy`](crate::ZipEq::zip_eq_lazy) otherwise.
+///
+/// The loop is driven by [`Iterator::try_fold`], so it benefits from the internal iteration of
+/// the zipped iterators. The patterns can be anything a `for` loop accepts, including `mut`
+/// bindings, and the loop can have a label. `break` and `continue` work as in a `for` loop, but
+/// `return` and `?` can't be used in the body.
+///
+/// [`TrustedLen`]: core::iter::TrustedLen
+///
+/// # Panics
+/// Panics if the inputs don't all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::for_zip_eq;
+///
+/// let a = [1, 2, 3, 4];
+/// let b = vec![10, 20, 30, 40];
+/// let mut out = [0; 4];
+/// for_zip_eq!((o, &a, mut b) in (&mut out, &a, b) {
+///     b += 1;
+///     *o = a + b;
+/// });
+/// assert_eq!(out, [12, 23, 34, 45]);
+///
+/// let grid = [[1, 2], [3, -4], [-5, 6]];
+/// let names = ["first", "second", "third"];
+/// let mut found = None;
+/// for_zip_eq!('rows: (row, name) in (&grid, names) {
+///     for &x in row {
+///         if x < 0 {
+///             found = Some(name);
+///             break 'rows;
+///         }
+///     }
+/// });
+/// assert_eq!(found, Some("second"));
+/// ```
+#[macro_export]
+macro_rules! for_zip_eq {
+    ($($label:lifetime:)? ($p:pat, $($ps:pat),+ $(,)?) in ($e:expr, $($es:expr),+ $(,)?) $body:block) => {
+        $crate::for_zip_eq!(
+            @zip [$($label)?] ($p), (::core::iter::IntoIterator::into_iter($e)),
+            ($($ps),+), ($($es),+), $body
+        )
+    };
+    (
+        @zip [$($label:lifetime)?] ($pat:pat), ($zip:expr),
+        ($p:pat $(, $ps:pat)*), ($e:expr $(, $es:expr)*), $body:block
+    ) => {
+        $crate::for_zip_eq!(
+            @zip [$($label)?]
+            (($pat, $p)),
+            ({
+                #[allow(unused_imports)]
+                use $crate::__private::{EagerPick as _, LazyPick as _};
+                (&&$crate::__private::Pick::new($zip, ::core::iter::IntoIterator::into_iter($e)))
+                    .zip_pick()
+            }),
+            ($($ps),*), ($($es),*), $body
+        )
+    };
+    (@zip [$($label:lifetime)?] ($pat:pat), ($zip:expr), (), (), $body:block) => {{
+        let mut zipped = $zip;
+        let _ = ::core::iter::Iterator::try_fold(&mut zipped, (), |(), $pat| {
+            // `break` exits the inner loop on its first pass and stops the iteration, while
+            // `continue` reaches the second pass, which moves on to the next elements.
+            let mut flow = ::core::ops::ControlFlow::Break(());
+            #[allow(clippy::never_loop)]
+            $($label:)? for first in [true, false] {
+                if first {
+                    $body
+                }
+                flow = ::core::ops::ControlFlow::Continue(());
+                break;
+            }
+            flow
+        });
+    }};
+}
+
+/// Support code for [`for_zip_eq!`](crate::for_zip_eq), not part of the public API.
+pub mod __private {
+    use core::cell::Cell;
+    use core::iter::TrustedLen;
+
+    use crate::{ZipEq, ZipEqEagerCheck, ZipEqLazyCheck};
+
+    /// Two iterators to zip, with the check picked by autoref specialization: [`EagerPick`]
+    /// applies if it can, and [`LazyPick`] otherwise.
+    pub struct Pick<A, B>(Cell<Option<(A, B)>>);
+
+    impl<A, B> Pick<A, B> {
+        pub fn new(a: A, b: B) -> Self {
+            Pick(Cell::new(Some((a, b))))
+        }
+
+        fn take(&self) -> (A, B) {
+            self.0
+                .take()
+                .expect("Pick: the iterators were already zipped")
+        }
+    }
+
+    pub trait EagerPick {
+        type Zip;
+        fn zip_pick(&self) -> Self::Zip;
+    }
+
+    impl<A, B> EagerPick for &Pick<A, B>
+    where
+        A: TrustedLen + ExactSizeIterator,
+        B: TrustedLen + ExactSizeIterator,
+    {
+        type Zip = ZipEqEagerCheck<A, B>;
+
+        fn zip_pick(&self) -> Self::Zip {
+            let (a, b) = self.take();
+            a.zip_eq_eager(b)
+        }
+    }
+
+    pub trait LazyPick {
+        type Zip;
+        fn zip_pick(&self) -> Self::Zip;
+    }
+
+    impl<A: Iterator, B: Iterator> LazyPick for &&Pick<A, B> {
+        type Zip = ZipEqLazyCheck<A, B>;
+
+        fn zip_pick(&self) -> Self::Zip {
+            let (a, b) = self.take();
+            a.zip_eq_lazy(b)
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use std::io::{self, BufRead, Read};
+
+use super::{try_zip_eq, LengthMismatch, TryZipEq, ZipError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Unit {
+    Line,
+    Byte,
+}
+
+/// Error wrapped in the [`io::Error`] yielded by [`ZipEqLines`] and [`ZipEqBytes`] when one input
+/// ends before the other. The error kind is [`io::ErrorKind::UnexpectedEof`].
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ReadMismatch;
+///
+/// let a = "a\nb\nc\n".as_bytes();
+/// let b = "d\ne\n".as_bytes();
+///
+/// let err = zip_eq::zip_eq_lines(a, b).last().unwrap().unwrap_err();
+/// let mismatch = err.get_ref().unwrap().downcast_ref::<ReadMismatch>().unwrap();
+/// assert_eq!(mismatch.line(), Some(3));
+/// assert_eq!(
+///     mismatch.to_string(),
+///     "right input ended before line 3 but left input continues"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadMismatch {
+    mismatch: LengthMismatch,
+    unit: Unit,
+}
+
+impl ReadMismatch {
+    /// Returns the underlying length mismatch, counted in lines or bytes.
+    pub fn mismatch(&self) -> LengthMismatch {
+        self.mismatch
+    }
+
+    /// Returns the 1-based number of the first line of the longer input that has no
+    /// counterpart, or `None` if the inputs were zipped byte by byte.
+    pub fn line(&self) -> Option<usize> {
+        match self.unit {
+            Unit::Line => Some(self.mismatch.index() + 1),
+            Unit::Byte => None,
+        }
+    }
+}
+
+impl fmt::Display for ReadMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let longer = self.mismatch.longer_side();
+        let shorter = longer.other();
+        match self.unit {
+            Unit::Line => write!(
+                f,
+                "{shorter} input ended before line {} but {longer} input continues",
+                self.mismatch.index() + 1,
+            ),
+            Unit::Byte => write!(
+                f,
+                "{shorter} input ended at byte offset {} but {longer} input continues",
+                self.mismatch.index(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReadMismatch {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.mismatch)
+    }
+}
+
+fn into_io_error(err: ZipError<io::Error, io::Error>, unit: Unit) -> io::Error {
+    match err {
+        ZipError::Left(err) | ZipError::Right(err) => err,
+        ZipError::Length(mismatch) => io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            ReadMismatch { mismatch, unit },
+        ),
+    }
+}
+
+/// Iterator over pairs of lines of two readers, created by [`zip_eq_lines`].
+#[derive(Debug)]
+pub struct ZipEqLines<A, B> {
+    inner: TryZipEq<io::Lines<A>, io::Lines<B>>,
+}
+
+/// Returns an iterator over pairs of lines of `a` and `b`, checking that they have the same number
+/// of lines during iteration.
+///
+/// Like [`BufRead::lines`], the lines don't include the newline characters. The iteration stops
+/// after the first error, which is either an I/O error from one of the readers, or an
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error wrapping a [`ReadMismatch`] if one
+/// reader ends before the other.
+///
+/// # Examples
+///
+/// ```
+/// let a = "1\n2\n".as_bytes();
+/// let b = "one\ntwo\n".as_bytes();
+///
+/// for pair in zip_eq::zip_eq_lines(a, b) {
+///     let (digit, word) = pair?;
+///     println!("{digit}: {word}");
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn zip_eq_lines<A: BufRead, B: BufRead>(a: A, b: B) -> ZipEqLines<A, B> {
+    ZipEqLines {
+        inner: try_zip_eq(a.lines(), b.lines()),
+    }
+}
+
+impl<A: BufRead, B: BufRead> Iterator for ZipEqLines<A, B> {
+    type Item = io::Result<(String, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.inner
+                .next()?
+                .map_err(|err| into_io_error(err, Unit::Line)),
+        )
+    }
+}
+
+impl<A: BufRead, B: BufRead> FusedIterator for ZipEqLines<A, B> {}
+
+/// Iterator over pairs of bytes of two readers, created by [`zip_eq_bytes`].
+#[derive(Debug)]
+pub struct ZipEqBytes<A, B> {
+    inner: TryZipEq<io::Bytes<A>, io::Bytes<B>>,
+}
+
+/// Returns an iterator over pairs of bytes of `a` and `b`, checking that they have the same number
+/// of bytes during iteration.
+///
+/// Like [`Read::bytes`], this reads one byte at a time, so the readers should be buffered. The
+/// iteration stops after the first error, which is either an I/O error from one of the readers,
+/// or an [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error wrapping a [`ReadMismatch`] if
+/// one reader ends before the other.
+///
+/// # Examples
+///
+/// ```
+/// let a = [1, 2, 3].as_slice();
+/// let b = [1, 5, 3].as_slice();
+///
+/// let differences = zip_eq::zip_eq_bytes(a, b)
+///     .filter(|pair| pair.as_ref().map_or(true, |(a, b)| a != b))
+///     .count();
+/// assert_eq!(differences, 1);
+/// ```
+// Buffering is left to the caller, as with `Read::bytes`.
+#[allow(clippy::unbuffered_bytes)]
+pub fn zip_eq_bytes<A: Read, B: Read>(a: A, b: B) -> ZipEqBytes<A, B> {
+    ZipEqBytes {
+        inner: try_zip_eq(a.bytes(), b.bytes()),
+    }
+}
+
+impl<A: Read, B: Read> Iterator for ZipEqBytes<A, B> {
+    type Item = io::Result<(u8, u8)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.inner
+                .next()?
+                .map_err(|err| into_io_error(err, Unit::Byte)),
+        )
+    }
+}
+
+impl<A: Read, B: Read> FusedIterator for ZipEqBytes<A, B> {}
@@ -0,0 +1,174 @@
+use core::iter::{FusedIterator, TrustedLen};
+use core::ops::Try;
+
+use super::{ZipEq, ZipEqEagerCheck, ZipEqLazyCheck};
+
+/// Iterator that gives a name to one of the zipped inputs, created by [`ZipEq::label`].
+///
+/// It yields the same items as the iterator it wraps. When the lengths of the zipped inputs
+/// don't match, the panic messages and [`LengthMismatch`](super::LengthMismatch) errors use its
+/// label instead of "left input" or "right input". See [`ZipEq::label`] for the adapters that
+/// use labels.
+#[derive(Debug, Clone)]
+pub struct Labeled<I> {
+    iter: I,
+    label: &'static str,
+}
+
+impl<I> Labeled<I> {
+    pub(crate) fn new(iter: I, label: &'static str) -> Self {
+        Labeled { iter, label }
+    }
+
+    /// Returns the label of the input.
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+/// Returns the labels of the zipped inputs, or `None` for those that aren't [`Labeled`].
+///
+/// It is implemented for every type, so that unlabeled inputs cost nothing.
+pub(crate) trait InputLabel {
+    fn input_label(&self) -> Option<&'static str>;
+}
+
+impl<T: ?Sized> InputLabel for T {
+    #[inline]
+    default fn input_label(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<I> InputLabel for Labeled<I> {
+    #[inline]
+    fn input_label(&self) -> Option<&'static str> {
+        Some(self.label)
+    }
+}
+
+// A zip of zips is named after its first input.
+impl<A, B> InputLabel for ZipEqEagerCheck<A, B> {
+    #[inline]
+    fn input_label(&self) -> Option<&'static str> {
+        self.a.input_label()
+    }
+}
+
+impl<A, B> InputLabel for ZipEqLazyCheck<A, B> {
+    #[inline]
+    fn input_label(&self) -> Option<&'static str> {
+        self.a.input_label()
+    }
+}
+
+impl<I: Iterator> Iterator for Labeled<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: Try<Output = B>,
+    {
+        self.iter.try_fold(init, f)
+    }
+
+    #[inline]
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.iter.fold(init, f)
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Labeled<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth_back(n)
+    }
+
+    #[inline]
+    fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: Try<Output = B>,
+    {
+        self.iter.try_rfold(init, f)
+    }
+
+    #[inline]
+    fn rfold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B {
+        self.iter.rfold(init, f)
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Labeled<I> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+// SAFETY: the items are the ones of `I`
+unsafe impl<I: TrustedLen> TrustedLen for Labeled<I> {}
+impl<I: FusedIterator> FusedIterator for Labeled<I> {}
+
+/// Returns a zipped iterator over two labeled inputs, after checking that their lengths are
+/// equal.
+///
+/// This is the same as `a.label(a_label).zip_eq_eager(b.label(b_label))`, and the panic message
+/// names the inputs by their labels.
+/// # Panics
+/// Panics if `a.len() != b.len()`
+///
+/// # Examples
+///
+/// ```
+/// let weights = [0.5, 0.25];
+/// let bias = vec![1.0, 2.0];
+/// let out: Vec<f64> = zip_eq::zip_eq_labeled(("weights", &weights), ("bias", &bias))
+///     .map(|(w, b)| w + b)
+///     .collect();
+///
+/// assert_eq!(out, [1.5, 2.25]);
+/// ```
+///
+/// ```should_panic
+/// // panics with "ZipEq: weights has 3 elements but bias has 2"
+/// zip_eq::zip_eq_labeled(("weights", [1, 2, 3]), ("bias", [4, 5]));
+/// ```
+pub fn zip_eq_labeled<A, B>(
+    (a_label, a): (&'static str, A),
+    (b_label, b): (&'static str, B),
+) -> ZipEqEagerCheck<Labeled<A::IntoIter>, Labeled<B::IntoIter>>
+where
+    A: IntoIterator,
+    A::IntoIter: TrustedLen + ExactSizeIterator,
+    B: IntoIterator,
+    B::IntoIter: TrustedLen + ExactSizeIterator,
+{
+    a.label(a_label).zip_eq_eager(b.label(b_label))
+}
@@ -1,5 +1,8 @@
 use core::iter::{FusedIterator, TrustedLen};
-use core::ops::Try;
+use core::ops::{ControlFlow, Try};
+
+use super::label::InputLabel;
+use super::Side;
 
 /// Iterator that zips two iterators, checking that they have the same length during
 /// iteration.
@@ -10,11 +13,66 @@ pub struct ZipEqLazyCheck<A, B> {
 }
 
 #[inline]
-fn both_or_none<T, U>(t: Option<T>, u: Option<U>) -> Option<(T, U)> {
+fn both_or_none<T, U>(
+    t: Option<T>,
+    u: Option<U>,
+    labels: [Option<&'static str>; 2],
+) -> Option<(T, U)> {
     match (t, u) {
         (Some(a), Some(b)) => Some((a, b)),
         (None, None) => None,
-        _ => super::panic_different_len(),
+        (Some(_), None) => super::panic_ended(Side::Left, labels),
+        (None, Some(_)) => super::panic_ended(Side::Right, labels),
+    }
+}
+
+/// Element of the longer input, read when the other input ended.
+pub(crate) enum Orphan<T, U> {
+    Left(T),
+    Right(U),
+}
+
+impl<A: Iterator, B: Iterator> ZipEqLazyCheck<A, B> {
+    /// Returns the labels of the inputs, which are `None` unless they are `Labeled`.
+    #[inline]
+    pub(crate) fn labels(&self) -> [Option<&'static str>; 2] {
+        [self.a.input_label(), self.b.input_label()]
+    }
+
+    /// Returns the next pair, or the side of the input that still had an element when the other
+    /// one ended. That element is dropped.
+    pub(crate) fn try_next(&mut self) -> Result<Option<<Self as Iterator>::Item>, Side> {
+        match (self.a.next(), self.b.next()) {
+            (Some(a), Some(b)) => Ok(Some((a, b))),
+            (None, None) => Ok(None),
+            (Some(_), None) => Err(Side::Left),
+            (None, Some(_)) => Err(Side::Right),
+        }
+    }
+
+    /// Like [`Iterator::try_fold`], but when one of the inputs ends before the other, passes the
+    /// element of the longer one to `f` as an [`Orphan`] instead of panicking, and stops.
+    #[inline]
+    pub(crate) fn try_fold_checked<I, F, R>(&mut self, init: I, mut f: F) -> R
+    where
+        F: FnMut(I, Result<<Self as Iterator>::Item, Orphan<A::Item, B::Item>>) -> R,
+        R: Try<Output = I>,
+    {
+        let b = &mut self.b;
+        let acc = self.a.try_fold(init, |acc, a| match b.next() {
+            Some(b) => match f(acc, Ok((a, b))).branch() {
+                ControlFlow::Continue(acc) => ControlFlow::Continue(acc),
+                ControlFlow::Break(residual) => ControlFlow::Break(R::from_residual(residual)),
+            },
+            None => ControlFlow::Break(f(acc, Err(Orphan::Left(a)))),
+        });
+        match acc {
+            ControlFlow::Continue(acc) => match b.next() {
+                Some(b) => f(acc, Err(Orphan::Right(b))),
+                None => R::from_output(acc),
+            },
+            ControlFlow::Break(r) => r,
+        }
     }
 }
 
@@ -22,7 +80,7 @@ impl<A: Iterator, B: Iterator> Iterator for ZipEqLazyCheck<A, B> {
     type Item = (A::Item, B::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        both_or_none(self.a.next(), self.b.next())
+        both_or_none(self.a.next(), self.b.next(), self.labels())
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -30,11 +88,12 @@ impl<A: Iterator, B: Iterator> Iterator for ZipEqLazyCheck<A, B> {
     }
 
     fn last(self) -> Option<Self::Item> {
-        both_or_none(self.a.last(), self.b.last())
+        let labels = self.labels();
+        both_or_none(self.a.last(), self.b.last(), labels)
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        both_or_none(self.a.nth(n), self.b.nth(n))
+        both_or_none(self.a.nth(n), self.b.nth(n), self.labels())
     }
 
     #[inline]
@@ -42,46 +101,44 @@ impl<A: Iterator, B: Iterator> Iterator for ZipEqLazyCheck<A, B> {
     where
         R: Try<Output = I>,
     {
-        let b = &mut self.b;
-        self.a.try_fold(init, move |init, a| {
-            f(
-                init,
-                (
-                    a,
-                    match b.next() {
-                        Some(b) => b,
-                        None => super::panic_different_len(),
-                    },
-                ),
-            )
+        let labels = self.labels();
+        self.try_fold_checked(init, |acc, item| match item {
+            Ok(pair) => f(acc, pair),
+            Err(Orphan::Left(_)) => super::panic_ended(Side::Left, labels),
+            Err(Orphan::Right(_)) => super::panic_ended(Side::Right, labels),
         })
     }
 
     #[inline]
     fn fold<I, F: FnMut(I, Self::Item) -> I>(self, init: I, mut f: F) -> I {
+        let labels = self.labels();
         let mut b = self.b;
-        self.a.fold(init, move |init, a| {
+        let acc = self.a.fold(init, |init, a| {
             f(
                 init,
                 (
                     a,
                     match b.next() {
                         Some(b) => b,
-                        None => super::panic_different_len(),
+                        None => super::panic_ended(Side::Left, labels),
                     },
                 ),
             )
-        })
+        });
+        if b.next().is_some() {
+            super::panic_ended(Side::Right, labels);
+        }
+        acc
     }
 }
 
 impl<A: DoubleEndedIterator, B: DoubleEndedIterator> DoubleEndedIterator for ZipEqLazyCheck<A, B> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        both_or_none(self.a.next_back(), self.b.next_back())
+        both_or_none(self.a.next_back(), self.b.next_back(), self.labels())
     }
 
     fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        both_or_none(self.a.nth_back(n), self.b.nth_back(n))
+        both_or_none(self.a.nth_back(n), self.b.nth_back(n), self.labels())
     }
 
     #[inline]
@@ -91,19 +148,29 @@ impl<A: DoubleEndedIterator, B: DoubleEndedIterator> DoubleEndedIterator for Zip
         F: FnMut(I, Self::Item) -> R,
         R: Try<Output = I>,
     {
+        let labels = self.labels();
         let b = &mut self.b;
-        self.a.try_rfold(init, move |init: I, a: A::Item| {
+        let acc = self.a.try_rfold(init, |init: I, a: A::Item| {
             f(
                 init,
                 (
                     a,
                     match b.next_back() {
                         Some(b) => b,
-                        None => super::panic_different_len(),
+                        None => super::panic_ended(Side::Left, labels),
                     },
                 ),
             )
-        })
+        });
+        match acc.branch() {
+            ControlFlow::Continue(acc) => {
+                if b.next_back().is_some() {
+                    super::panic_ended(Side::Right, labels);
+                }
+                R::from_output(acc)
+            }
+            ControlFlow::Break(residual) => R::from_residual(residual),
+        }
     }
 
     #[inline]
@@ -112,19 +179,24 @@ impl<A: DoubleEndedIterator, B: DoubleEndedIterator> DoubleEndedIterator for Zip
         Self: Sized,
         F: FnMut(I, Self::Item) -> I,
     {
+        let labels = self.labels();
         let mut b = self.b;
-        self.a.rfold(init, move |init, a| {
+        let acc = self.a.rfold(init, |init, a| {
             f(
                 init,
                 (
                     a,
                     match b.next_back() {
                         Some(b) => b,
-                        None => super::panic_different_len(),
+                        None => super::panic_ended(Side::Left, labels),
                     },
                 ),
             )
-        })
+        });
+        if b.next_back().is_some() {
+            super::panic_ended(Side::Right, labels);
+        }
+        acc
     }
 }
 
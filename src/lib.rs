@@ -1,5 +1,17 @@
 #![feature(trusted_len)]
+#![feature(async_iterator)]
+#![feature(extend_one)]
+#![feature(inplace_iteration)]
+// Used by:
+// - the `SourceIter` and `InPlaceIterable` impls of `ZipEqEagerCheck` (eager.rs), which are
+//   specialization traits,
+// - `InputLabel::input_label` (label.rs), specialized for `Labeled` inputs and the zips,
+// - `ExactLen::exact_len` (map_eq.rs), specialized for `TrustedLen` inputs.
+#![feature(min_specialization)]
+#![feature(slice_range)]
 #![feature(try_trait_v2)]
+#![feature(vec_deque_iter_as_slices)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![deny(unsafe_op_in_unsafe_fn)]
 
 //! Zip iterator that check that its inputs have the same length.
@@ -14,6 +26,81 @@
 //! The second type of iterator is one that checks that the sizes are equal while it's being
 //! iterated over. It can be constructed with [`ZipEq::zip_eq_lazy`].
 //!
+//! [`for_zip_eq!`] loops over several inputs with flat bindings, checking their lengths eagerly
+//! when it can.
+//!
+//! Inputs can be named with [`ZipEq::label`] or [`zip_eq_labeled`], so that length mismatch
+//! panics and errors say which inputs differ.
+//!
+//! [`zip_eq_by_key`] also checks that the elements at each position have the same key, for
+//! inputs that describe the same entities in the same order.
+//!
+//! Maps that must have the same keys can be zipped with [`zip_eq_maps`], for `BTreeMap`s and
+//! other inputs sorted by key, or [`zip_eq_hash_maps`], which report the first key that is in
+//! only one of them.
+//!
+//! [`collect_map_eq`] builds a `HashMap` or `BTreeMap` from separate keys and values, and
+//! reports a length mismatch or a duplicate key instead of dropping data.
+//!
+//! Nested containers such as `Vec<Vec<T>>` can be zipped row by row with [`zip_eq_nested`],
+//! which checks the shape of each pair of rows at any depth and reports the path to the first
+//! difference. [`check_shape`] compares whole containers.
+//!
+//! Any number of iterators of the same type can be zipped lazily with [`zip_eq_all`].
+//! [`transpose_eq`] and [`transpose_eq_lazy`] use it to turn rows into columns, and report the
+//! first ragged row.
+//!
+//! [`first_difference`] and [`first_difference_by`] compare two sequences and report where they
+//! diverged: the first pair of different elements, or the point where one of them ended.
+//!
+//! [`assert_same_len!`] and [`assert_iter_eq!`] print the expressions, lengths and first
+//! difference of their arguments when they fail.
+//!
+//! Iterators of `Result`s can be zipped with [`try_zip_eq`], which stops at the first error,
+//! whether it comes from one of the inputs or from a length mismatch.
+//!
+//! Readers can be zipped line by line with [`zip_eq_lines`], or byte by byte with
+//! [`zip_eq_bytes`].
+//!
+//! Messages from two [`mpsc`](std::sync::mpsc) channels can be paired with
+//! [`zip_eq_receivers`] and [`zip_eq_receivers_timeout`].
+//!
+//! Slices can also be zipped chunk by chunk after a single length check with [`zip_eq_chunks`],
+//! which yields pairs of arrays with a length known at compile time. With the `simd` feature,
+//! [`zip_eq_simd`] loads those chunks into [`core::simd::Simd`] vectors.
+//!
+//! Strings can be zipped character by character with [`zip_eq_chars`], which checks the
+//! character counts once instead of at every step.
+//!
+//! With the `rayon` feature, [`ParZipEq`] and [`ParZipEqAll`] provide the eager check for
+//! indexed parallel iterators.
+//!
+//! Asynchronous sequences can be zipped lazily with [`zip_eq_async_iter`] and, with the
+//! `futures` feature, [`zip_eq_stream`] and [`zip_eq_stream_checked`].
+//!
+//! The inverse operation is done by [`unzip_eq`], [`extend_pairs`] and [`ExtendEq`], which
+//! reserve room for the exact length of a [`TrustedLen`] iterator of tuples in each collection.
+//!
+//! Parallel slices can be sorted together with [`co_sort`] and its variants, reordered with
+//! [`apply_permutation`], or shuffled with [`co_shuffle`].
+//!
+//! Elements can be removed from two parallel `Vec`s without misaligning them with
+//! [`retain_pairs`], [`dedup_pairs_by`], [`drain_pairs`] and [`truncate_pairs`].
+//!
+//! The [`num`] module provides numeric kernels, such as dot products and distances, built on
+//! the eager check.
+//!
+//! [`PairVec`] and [`ColumnVec3`] to [`ColumnVec6`] store several vectors whose lengths are
+//! kept equal, so iterating over their rows needs no check.
+//!
+//! With the `derive` feature, [`ZipEqRows`] generates row iterators for structs made of `Vec`
+//! fields that must have the same length.
+//!
+//! Iterators over containers made of several contiguous parts, such as
+//! [`VecDeque`](std::collections::VecDeque), implement [`SegmentedIterator`]. When both sides of
+//! a [`ZipEqEagerCheck`] do, [`ZipEqEagerCheck::for_each_segmented`] iterates over aligned
+//! pairs of contiguous sub-slices, which the compiler can vectorize.
+//!
 //! # Examples:
 //!
 //! ```
@@ -62,17 +149,102 @@
 
 use std::iter::TrustedLen;
 
+use error::InputName;
+use label::InputLabel;
+
+mod assert;
+mod by_key;
+mod channel;
+mod chars;
+mod chunks;
+mod columns;
+mod diff;
 mod eager;
+mod error;
+mod fallible;
+mod for_zip;
+mod io;
+mod label;
 mod lazy;
+mod map_eq;
+mod maps;
+mod nary;
+mod nested;
+pub mod num;
+#[cfg(feature = "rayon")]
+mod par;
+mod permute;
+mod segments;
+mod stream;
+mod transpose;
+mod unzip;
+mod vec_pairs;
 
+// Support code for the macros, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use super::assert::__private::*;
+    pub use super::for_zip::__private::*;
+}
+pub use by_key::*;
+pub use channel::*;
+pub use chars::*;
+pub use chunks::*;
+pub use columns::*;
+pub use diff::*;
 pub use eager::*;
+pub use error::*;
+pub use fallible::*;
+pub use io::*;
+pub use label::*;
 pub use lazy::*;
+pub use map_eq::*;
+pub use maps::*;
+pub use nary::*;
+pub use nested::*;
+#[cfg(feature = "rayon")]
+pub use par::*;
+pub use permute::*;
+pub use segments::*;
+pub use stream::*;
+pub use transpose::*;
+pub use unzip::*;
+pub use vec_pairs::*;
+#[cfg(feature = "derive")]
+pub use zip_eq_derive::ZipEqRows;
+
+// Lets the tests use the derive macros, whose output refers to `::zip_eq`.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as zip_eq;
 
 #[cold]
 fn panic_different_len() -> ! {
     panic!("ZipEq: Reached the end of one of the iterators before the other.");
 }
 
+/// Panics with the lengths of two inputs, naming them if they are [`Labeled`].
+#[cold]
+fn panic_len_mismatch(err: LengthMismatch) -> ! {
+    if err.left_label().is_none() && err.right_label().is_none() {
+        panic_different_len();
+    }
+    panic!("ZipEq: {err}");
+}
+
+/// Panics because the input on the other side of `longer` ended first, naming the inputs if they
+/// are [`Labeled`].
+#[cold]
+fn panic_ended(longer: Side, [left, right]: [Option<&'static str>; 2]) -> ! {
+    if left.is_none() && right.is_none() {
+        panic_different_len();
+    }
+    let (shorter, longer) = match longer {
+        Side::Left => (InputName(right, Side::Right), InputName(left, Side::Left)),
+        Side::Right => (InputName(left, Side::Left), InputName(right, Side::Right)),
+    };
+    panic!("ZipEq: {shorter} ended before {longer}");
+}
+
 fn size_hint_impl(a: (usize, Option<usize>), b: (usize, Option<usize>)) -> (usize, Option<usize>) {
     (
         a.0.max(b.0),
@@ -88,6 +260,7 @@ fn size_hint_impl(a: (usize, Option<usize>), b: (usize, Option<usize>)) -> (usiz
 /// Trait that adds `zip_eq_*` builder functions to objects that are convertible to iterators
 pub trait ZipEq {
     /// Returns a zipped iterator without checking that the lengths of the iterators are equal.
+    /// # Safety
     /// The behavior is undefined if the iterators don't have the same length.
     unsafe fn zip_eq_unchecked<B>(self, b: B) -> ZipEqEagerCheck<Self::IntoIter, B::IntoIter>
     where
@@ -114,6 +287,29 @@ pub trait ZipEq {
     where
         Self: IntoIterator,
         B: IntoIterator;
+
+    /// Returns an iterator that yields the same items, but is named `label` in the panic
+    /// messages and errors of the zips it's part of.
+    ///
+    /// Labels are used by the sequential two-input zips: [`ZipEq::zip_eq_eager`],
+    /// [`ZipEq::zip_eq_lazy`], [`for_zip_eq!`], [`try_zip_eq`], [`zip_eq_by_key`],
+    /// [`zip_eq_maps`] and [`collect_map_eq`]. The other adapters either take inputs that can't be
+    /// labeled, such as parallel iterators, streams, channels and `HashMap`s, or report
+    /// mismatches by position, such as [`zip_eq_all`].
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use zip_eq::ZipEq;
+    ///
+    /// let weights = vec![0.5; 10];
+    /// let bias = vec![0.0; 9];
+    /// // panics with "ZipEq: weights has 10 elements but bias has 9"
+    /// let zipped = weights.label("weights").zip_eq_eager(bias.label("bias"));
+    /// ```
+    fn label(self, label: &'static str) -> Labeled<Self::IntoIter>
+    where
+        Self: IntoIterator;
 }
 
 impl<A: IntoIterator> ZipEq for A {
@@ -141,7 +337,9 @@ impl<A: IntoIterator> ZipEq for A {
         let b = b.into_iter();
 
         if a.len() != b.len() {
-            panic_different_len();
+            panic_len_mismatch(
+                LengthMismatch::new(a.len(), b.len()).with_labels(a.input_label(), b.input_label()),
+            );
         }
         ZipEqEagerCheck { a, b }
     }
@@ -156,6 +354,13 @@ impl<A: IntoIterator> ZipEq for A {
             b: b.into_iter(),
         }
     }
+
+    fn label(self, label: &'static str) -> Labeled<A::IntoIter>
+    where
+        A: IntoIterator,
+    {
+        Labeled::new(self.into_iter(), label)
+    }
 }
 
 #[cfg(test)]
@@ -354,6 +559,34 @@ mod tests {
             zipped.next();
         }
 
+        #[test]
+        #[should_panic]
+        fn left_shorter_for_each() {
+            [1].zip_eq_lazy((0..3).filter(|_| true)).for_each(drop);
+        }
+
+        #[test]
+        #[should_panic]
+        fn left_shorter_try_fold() {
+            let mut zipped = [1, 2].zip_eq_lazy((0..3).filter(|_| true));
+            let _ = zipped.try_fold(0_i32, |acc, (a, b)| acc.checked_add(a + b));
+        }
+
+        #[test]
+        #[should_panic]
+        fn left_shorter_rfold() {
+            [1].zip_eq_lazy(vec![2, 3]).rfold((), |(), _| ());
+        }
+
+        #[test]
+        fn try_fold_break_is_not_a_mismatch() {
+            let mut zipped = [1, 2].zip_eq_lazy(0..3);
+            assert_eq!(
+                zipped.try_fold(0, |acc, (a, _)| (acc == 0).then_some(a)),
+                None
+            );
+        }
+
         #[test]
         fn count() {
             let a = [1, 2];
@@ -500,4 +733,1879 @@ mod tests {
             );
         }
     }
+
+    mod chunks {
+        use super::*;
+        #[test]
+        fn basic() {
+            let a = [1, 2, 3, 4, 5];
+            let b = [6, 7, 8, 9, 10];
+            let mut zipped = zip_eq_chunks::<2, _, _>(&a, &b);
+
+            assert_eq!(zipped.len(), 2);
+            assert_eq!(zipped.next(), Some((&[1, 2], &[6, 7])));
+            assert_eq!(zipped.next(), Some((&[3, 4], &[8, 9])));
+            assert_eq!(zipped.next(), None);
+            assert_eq!(zipped.remainder(), (&[5][..], &[10][..]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn basic_fail() {
+            let a = [1, 2, 3];
+            let b = [3, 4];
+            let _zipped = zip_eq_chunks::<2, _, _>(&a, &b);
+        }
+
+        #[test]
+        fn exact() {
+            let a = [1, 2, 3, 4];
+            let b = [5, 6, 7, 8];
+            let zipped = zip_eq_chunks::<2, _, _>(&a, &b);
+
+            assert_eq!(zipped.remainder(), (&[][..], &[][..]));
+            assert_eq!(zipped.rfold(0, |acc, (a, b)| 10 * acc + a[0] * b[1]), 246,);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    mod simd {
+        use super::*;
+        use core::simd::Simd;
+
+        #[test]
+        fn basic() {
+            let a = [1, 2, 3, 4, 5];
+            let b = [6_u8, 7, 8, 9, 10];
+            let mut zipped = zip_eq_simd::<2, _, _>(&a, &b);
+
+            assert_eq!(
+                zipped.next(),
+                Some((Simd::from_array([1, 2]), Simd::from_array([6, 7])))
+            );
+            assert_eq!(
+                zipped.next_back(),
+                Some((Simd::from_array([3, 4]), Simd::from_array([8, 9])))
+            );
+            assert_eq!(zipped.next(), None);
+            assert_eq!(zipped.remainder(), (&[5][..], &[10][..]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn basic_fail() {
+            let a = [1, 2, 3];
+            let b = [3, 4];
+            let _zipped = zip_eq_simd::<2, i32, i32>(&a, &b);
+        }
+    }
+
+    mod segments {
+        use super::*;
+        use std::collections::VecDeque;
+
+        fn deque(values: &[i32], front: usize) -> VecDeque<i32> {
+            let mut deque: VecDeque<_> = values[front..].iter().copied().collect();
+            for &v in values[..front].iter().rev() {
+                deque.push_front(v);
+            }
+            deque
+        }
+
+        #[test]
+        fn vec_deque() {
+            let values = [1, 2, 3, 4, 5, 6, 7];
+            let a = deque(&values, 2);
+            let mut b = deque(&values, 5);
+            assert_ne!(a.as_slices().0.len(), b.as_slices().0.len());
+
+            let mut lengths = vec![];
+            a.iter()
+                .zip_eq_eager(b.iter_mut())
+                .fold_segments((), |(), a, b| {
+                    lengths.push(a.len());
+                    for (a, b) in a.iter().zip(b) {
+                        *b += a;
+                    }
+                });
+
+            assert_eq!(lengths, [2, 3, 2]);
+            assert!(b.iter().copied().eq(values.iter().map(|v| 2 * v)));
+        }
+
+        #[test]
+        fn fold_segmented() {
+            let a = deque(&[1, 2, 3], 1);
+            let b = [4, 5, 6];
+            let mut factor = 0;
+
+            assert_eq!(
+                a.iter()
+                    .zip_eq_eager(&b)
+                    .fold_segmented((0, 0), |(acc_a, acc_b), (a, b)| {
+                        factor += 1;
+                        (acc_a + factor * a, acc_b + factor * b)
+                    }),
+                (14, 32),
+            );
+        }
+
+        #[test]
+        fn nested() {
+            let a = deque(&[1, 2, 3, 4], 1);
+            let b = deque(&[5, 6, 7, 8], 3);
+            let mut out = deque(&[0; 4], 2);
+            let mut lengths = vec![];
+
+            out.iter_mut()
+                .zip_eq_eager(a.iter().zip_eq_eager(b.iter()))
+                .fold_segments((), |(), out, ab| {
+                    lengths.push(out.len());
+                    for (o, (a, b)) in out.iter_mut().zip(ab) {
+                        *o = a + b;
+                    }
+                });
+
+            assert_eq!(lengths, [1, 1, 1, 1]);
+            assert!(out.iter().eq(&[6, 8, 10, 12]));
+        }
+
+        #[test]
+        fn chain() {
+            let a = chain_segments(&[1, 2, 3], &[4]);
+            let b = chain_segments(&[5], &[6, 7, 8]);
+            let mut lengths = vec![];
+            let mut pairs = vec![];
+
+            a.zip_eq_eager(b).fold_segments((), |(), a, b| {
+                lengths.push(a.len());
+                pairs.extend(a.iter().zip(b));
+            });
+
+            assert_eq!(lengths, [1, 2, 1]);
+            assert_eq!(pairs, [(&1, &5), (&2, &6), (&3, &7), (&4, &8)]);
+        }
+
+        #[test]
+        fn flatten() {
+            let a = vec![vec![1, 2], vec![], vec![3]];
+            let mut b = vec![vec![0], vec![0, 0]];
+            let mut flat = flatten_segments(&a);
+            assert_eq!(flat.len(), 3);
+            assert_eq!(flat.next(), Some(&1));
+
+            flat.zip_eq_lazy(flatten_segments_mut(&mut b).skip(1))
+                .for_each(|(a, b)| *b = *a);
+            assert_eq!(b, [vec![0], vec![2, 3]]);
+
+            flatten_segments(&a)
+                .zip_eq_eager(flatten_segments_mut(&mut b))
+                .for_each_segmented(|(a, b)| *b = *a);
+            assert_eq!(b, [vec![1], vec![2, 3]]);
+
+            let rows: [&[i32]; 2] = [&[4], &[5, 6]];
+            flatten_segments(&rows)
+                .zip_eq_eager(flatten_segments_mut(&mut b))
+                .for_each_segmented(|(a, b)| *b = *a);
+            assert_eq!(b, [vec![4], vec![5, 6]]);
+        }
+
+        #[test]
+        fn overridden_is_empty() {
+            use core::{option, slice};
+
+            // Claims to be empty, which the invariants of `Segment` allow.
+            struct Lying<'a>(&'a [i32]);
+            impl<'a> IntoIterator for Lying<'a> {
+                type Item = &'a i32;
+                type IntoIter = slice::Iter<'a, i32>;
+                fn into_iter(self) -> Self::IntoIter {
+                    self.0.iter()
+                }
+            }
+            unsafe impl Segment for Lying<'_> {
+                fn len(&self) -> usize {
+                    self.0.len()
+                }
+                fn is_empty(&self) -> bool {
+                    true
+                }
+                fn split_at(self, mid: usize) -> (Self, Self) {
+                    let (head, tail) = self.0.split_at(mid);
+                    (Lying(head), Lying(tail))
+                }
+            }
+            struct LyingIter<'a>(slice::Iter<'a, i32>);
+            impl<'a> Iterator for LyingIter<'a> {
+                type Item = &'a i32;
+                fn next(&mut self) -> Option<Self::Item> {
+                    self.0.next()
+                }
+            }
+            unsafe impl<'a> SegmentedIterator for LyingIter<'a> {
+                type Segment = Lying<'a>;
+                type Segments = option::IntoIter<Lying<'a>>;
+                fn into_segments(self) -> Self::Segments {
+                    Some(Lying(self.0.as_slice())).into_iter()
+                }
+            }
+
+            let a = deque(&[1, 2, 3], 1);
+            let b = [4, 5, 6];
+            // SAFETY: both inputs have 3 elements
+            let zipped = ZipEqEagerCheck {
+                a: a.iter(),
+                b: LyingIter(b.iter()),
+            };
+            let sums = zipped.fold_segmented(vec![], |mut acc, (a, b)| {
+                acc.push(a + b);
+                acc
+            });
+            assert_eq!(sums, [5, 7, 9]);
+        }
+
+        #[test]
+        #[should_panic = "SegmentPair::split_at: mid > len"]
+        fn pair_split_out_of_bounds() {
+            let (a, b) = ([1, 2], [3, 4]);
+            let pair = a.iter().zip_eq_eager(&b).into_segments().next().unwrap();
+            let _ = pair.split_at(3);
+        }
+
+        #[test]
+        fn flatten_untrusted() {
+            use std::cell::Cell;
+
+            // Returns a longer slice on every call.
+            struct Growing(Cell<usize>, [i32; 8]);
+            impl AsRef<[i32]> for Growing {
+                fn as_ref(&self) -> &[i32] {
+                    self.0.set(self.0.get() + 1);
+                    &self.1[..self.0.get()]
+                }
+            }
+
+            let rows = [Growing(Cell::new(0), [1; 8]), Growing(Cell::new(0), [2; 8])];
+            let flat = flatten_segments(&rows);
+            assert_eq!(flat.len(), 2);
+            let pairs = flat.zip_eq_lazy(0..5).take(3).count();
+            assert_eq!(pairs, 3);
+        }
+    }
+
+    mod chars {
+        use super::*;
+        #[test]
+        fn ascii() {
+            let zipped = zip_eq_chars("abc", "def");
+            assert_eq!(
+                zipped.collect::<Vec<_>>(),
+                [('a', 'd'), ('b', 'e'), ('c', 'f')]
+            );
+        }
+
+        #[test]
+        fn unicode() {
+            let zipped = zip_eq_chars("añb", "çdé");
+            assert_eq!(zipped.last(), Some(('b', 'é')));
+        }
+
+        #[test]
+        #[should_panic]
+        fn same_bytes_different_chars() {
+            let _zipped = zip_eq_chars("ñ", "ab");
+        }
+
+        #[test]
+        fn char_indices() {
+            let mut zipped = zip_eq_char_indices("ñb", "cd");
+            assert_eq!(zipped.next_back(), Some(((2, 'b'), (1, 'd'))));
+            assert_eq!(zipped.next(), Some(((0, 'ñ'), (0, 'c'))));
+            assert_eq!(zipped.next(), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn char_indices_fail() {
+            let _zipped = zip_eq_char_indices("abc", "ab");
+        }
+    }
+
+    mod error {
+        use super::*;
+        #[test]
+        fn display() {
+            assert_eq!(
+                LengthMismatch::new(3, 2).to_string(),
+                "left input has 3 elements but right input has 2"
+            );
+            assert_eq!(
+                LengthMismatch::at(2, Side::Left).to_string(),
+                "right input ended after 2 elements but left input has more"
+            );
+        }
+
+        #[test]
+        fn lengths() {
+            let err = LengthMismatch::at(4, Side::Right);
+            assert_eq!(err.index(), 4);
+            assert_eq!((err.left_len(), err.right_len()), (Some(4), None));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    mod par {
+        use super::*;
+        use rayon::prelude::*;
+
+        #[test]
+        fn basic() {
+            let a: Vec<_> = (0..1000).collect();
+            let b: Vec<_> = (1000..2000).collect();
+
+            let sums: Vec<_> = a
+                .par_iter()
+                .par_zip_eq_eager(&b)
+                .with_max_len(7)
+                .map(|(a, b)| a + b)
+                .collect();
+            assert!(sums.iter().copied().eq((0..1000).map(|i| 1000 + 2 * i)));
+        }
+
+        #[test]
+        #[should_panic = "ZipEq: left input has 3 elements but right input has 2"]
+        fn basic_fail() {
+            let a = vec![1, 2, 3];
+            let b = vec![3, 4];
+            let _zipped = a.par_zip_eq_eager(b);
+        }
+
+        #[test]
+        fn try_par_zip_eq() {
+            let a = vec![1, 2];
+            let b = vec![3, 4];
+            assert_eq!(
+                a.try_par_zip_eq(b).unwrap().rev().collect::<Vec<_>>(),
+                [(2, 4), (1, 3)]
+            );
+
+            let a = vec![1, 2];
+            let b = vec![3, 4, 5];
+            let err = a.try_par_zip_eq(b).unwrap_err();
+            assert_eq!(err.longer_side(), Side::Right);
+            assert_eq!((err.left_len(), err.right_len()), (Some(2), Some(3)));
+        }
+
+        #[test]
+        fn all() {
+            let a: Vec<_> = (0..100).collect();
+            let b: Vec<_> = (100..200).collect();
+            let c: Vec<_> = (200..300).collect();
+
+            let sum: i32 = (&a, &b, c)
+                .par_zip_eq_all()
+                .map(|(a, b, c)| a + b + c)
+                .sum();
+            assert_eq!(sum, 3 * 4950 + 30000);
+        }
+
+        #[test]
+        #[should_panic = "ZipEq: input 2 ended after 1 elements but input 0 has more"]
+        fn all_fail() {
+            let a = [1, 2];
+            let b = [3, 4];
+            let c = [5];
+            let _zipped = (a, b, c).par_zip_eq_all();
+        }
+
+        #[test]
+        fn try_all() {
+            let (a, b, c) = (vec![1, 2], [3, 4], [5, 6]);
+            let zipped = (&a, &b, &c).try_par_zip_eq_all().unwrap();
+            assert_eq!(zipped.collect::<Vec<_>>(), [(&1, &3, &5), (&2, &4, &6)]);
+
+            let err = (&a, &b, &c[..1], &a).try_par_zip_eq_all().unwrap_err();
+            assert_eq!(err, InputsMismatch::new(1, 2, 0));
+        }
+    }
+
+    mod stream {
+        use super::*;
+        use core::async_iter::AsyncIterator;
+        use core::pin::{pin, Pin};
+        use core::task::{Context, Poll, Waker};
+
+        /// Yields the items of an iterator, returning `Pending` before every `delay`-th poll.
+        struct Delayed<I> {
+            iter: I,
+            delay: usize,
+            polls: usize,
+        }
+
+        impl<I: Iterator + Unpin> Delayed<I> {
+            fn new(iter: impl IntoIterator<IntoIter = I>, delay: usize) -> Self {
+                Delayed {
+                    iter: iter.into_iter(),
+                    delay,
+                    polls: 0,
+                }
+            }
+
+            fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+                self.polls += 1;
+                if self.polls.is_multiple_of(self.delay) {
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                Poll::Ready(self.iter.next())
+            }
+        }
+
+        impl<I: Iterator + Unpin> AsyncIterator for Delayed<I> {
+            type Item = I::Item;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+                self.get_mut().poll(cx)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        #[cfg(feature = "futures")]
+        impl<I: Iterator + Unpin> futures_core::Stream for Delayed<I> {
+            type Item = I::Item;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+                self.get_mut().poll(cx)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        fn collect<T>(mut poll_next: impl FnMut(&mut Context<'_>) -> Poll<Option<T>>) -> Vec<T> {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut items = vec![];
+            loop {
+                match poll_next(&mut cx) {
+                    Poll::Ready(Some(item)) => items.push(item),
+                    Poll::Ready(None) => return items,
+                    Poll::Pending => {}
+                }
+            }
+        }
+
+        #[test]
+        fn async_iter() {
+            let mut zipped = pin!(zip_eq_async_iter(
+                Delayed::new([1, 2, 3], 2),
+                Delayed::new([4, 5, 6], 3),
+            ));
+            assert_eq!(zipped.size_hint(), (3, Some(3)));
+            assert_eq!(
+                collect(|cx| zipped.as_mut().poll_next(cx)),
+                [(1, 4), (2, 5), (3, 6)]
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn async_iter_fail() {
+            let mut zipped = pin!(zip_eq_async_iter(
+                Delayed::new([1, 2, 3], 2),
+                Delayed::new([4, 5], 3),
+            ));
+            collect(|cx| zipped.as_mut().poll_next(cx));
+        }
+
+        #[cfg(feature = "futures")]
+        #[test]
+        fn stream() {
+            use futures_core::{FusedStream, Stream};
+
+            let mut zipped = pin!(zip_eq_stream(
+                Delayed::new([1, 2], 3),
+                Delayed::new([4, 5], 2),
+            ));
+            assert_eq!(
+                collect(|cx| zipped.as_mut().poll_next(cx)),
+                [(1, 4), (2, 5)]
+            );
+            assert!(zipped.is_terminated());
+        }
+
+        #[cfg(feature = "futures")]
+        #[test]
+        #[should_panic]
+        fn stream_fail() {
+            use futures_core::Stream;
+
+            let mut zipped = pin!(zip_eq_stream(Delayed::new([1], 3), Delayed::new([4, 5], 2),));
+            collect(|cx| zipped.as_mut().poll_next(cx));
+        }
+
+        #[cfg(feature = "futures")]
+        #[test]
+        fn stream_checked() {
+            use futures_core::Stream;
+
+            let mut zipped = pin!(zip_eq_stream_checked(
+                Delayed::new([1, 2, 3], 3),
+                Delayed::new([4, 5], 2),
+            ));
+            assert_eq!(
+                collect(|cx| zipped.as_mut().poll_next(cx)),
+                [
+                    Ok((1, 4)),
+                    Ok((2, 5)),
+                    Err(LengthMismatch::at(2, Side::Left))
+                ]
+            );
+        }
+    }
+
+    mod fallible {
+        use super::*;
+
+        type Zipped =
+            TryZipEq<std::vec::IntoIter<Result<u8, char>>, std::vec::IntoIter<Result<u8, char>>>;
+
+        fn zip(a: Vec<Result<u8, char>>, b: Vec<Result<u8, char>>) -> Zipped {
+            try_zip_eq(a, b)
+        }
+
+        #[test]
+        fn basic() {
+            let zipped = zip(vec![Ok(1), Ok(2)], vec![Ok(3), Ok(4)]);
+            assert_eq!(
+                zipped.collect::<Result<Vec<_>, _>>(),
+                Ok(vec![(1, 3), (2, 4)])
+            );
+        }
+
+        #[test]
+        fn source_error() {
+            let mut zipped = zip(vec![Ok(1), Ok(2), Ok(3)], vec![Ok(3), Err('b'), Ok(5)]);
+            assert_eq!(zipped.next(), Some(Ok((1, 3))));
+            assert_eq!(zipped.next(), Some(Err(ZipError::Right('b'))));
+            assert_eq!(zipped.next(), None);
+
+            let mut zipped = zip(vec![Err('a')], vec![Err('b')]);
+            assert_eq!(zipped.next().unwrap().unwrap_err().side(), Some(Side::Left));
+        }
+
+        #[test]
+        fn length_mismatch() {
+            let mut zipped = zip(vec![Ok(1)], vec![Ok(3), Ok(4)]);
+            assert_eq!(zipped.next(), Some(Ok((1, 3))));
+            assert_eq!(
+                zipped.next(),
+                Some(Err(ZipError::Length(LengthMismatch::at(1, Side::Right))))
+            );
+            assert_eq!(zipped.next(), None);
+        }
+
+        #[test]
+        fn fold() {
+            let zipped = zip(vec![Ok(1), Ok(2), Ok(3)], vec![Ok(4), Ok(5)]);
+            assert_eq!(
+                zipped.fold(vec![], |mut acc, item| {
+                    acc.push(item);
+                    acc
+                }),
+                [
+                    Ok((1, 4)),
+                    Ok((2, 5)),
+                    Err(ZipError::Length(LengthMismatch::at(2, Side::Left)))
+                ],
+            );
+        }
+
+        #[test]
+        fn try_fold() {
+            let mut zipped = zip(vec![Ok(1), Ok(2), Ok(3)], vec![Ok(4), Ok(5), Ok(6)]);
+            assert_eq!(
+                zipped.try_fold(0, |acc, item| {
+                    let (a, b) = item.ok()?;
+                    (a < 2).then_some(acc + a * b)
+                }),
+                None,
+            );
+            assert_eq!(zipped.next(), Some(Ok((3, 6))));
+            assert_eq!(zipped.next(), None);
+        }
+
+        #[test]
+        fn fold_orphan() {
+            let collect = |zipped: Zipped| zipped.collect::<Vec<_>>();
+            assert_eq!(
+                collect(zip(vec![Ok(1)], vec![Ok(2), Err('b'), Ok(3)])),
+                [Ok((1, 2)), Err(ZipError::Right('b'))],
+            );
+            assert_eq!(
+                collect(zip(vec![Ok(1)], vec![Ok(2), Ok(3)])),
+                [
+                    Ok((1, 2)),
+                    Err(ZipError::Length(LengthMismatch::at(1, Side::Right)))
+                ],
+            );
+        }
+
+        #[test]
+        fn try_fold_fail() {
+            let mut zipped = zip(vec![Ok(1), Err('a'), Ok(3)], vec![Ok(4), Ok(5), Ok(6)]);
+            assert_eq!(
+                zipped.try_fold(0, |acc, item| item.map(|(a, b)| acc + a * b)),
+                Err(ZipError::Left('a')),
+            );
+            assert_eq!(zipped.next(), None);
+        }
+    }
+
+    mod channel {
+        use super::*;
+        use std::sync::mpsc;
+        use std::thread;
+        use std::time::Duration;
+
+        #[test]
+        fn basic() {
+            let (tx_a, rx_a) = mpsc::channel();
+            let (tx_b, rx_b) = mpsc::sync_channel(1);
+            let sender = thread::spawn(move || {
+                for i in 0..100 {
+                    tx_b.send(i + 1).unwrap();
+                    tx_a.send(i).unwrap();
+                }
+            });
+
+            assert!(zip_eq_receivers(rx_a, rx_b).all(|(a, b)| a + 1 == b));
+            sender.join().unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn basic_fail() {
+            let (tx_a, rx_a) = mpsc::channel();
+            let (tx_b, rx_b) = mpsc::channel();
+            tx_a.send(1).unwrap();
+            tx_a.send(2).unwrap();
+            tx_b.send(1).unwrap();
+            drop((tx_a, tx_b));
+            zip_eq_receivers(rx_a, rx_b).for_each(drop);
+        }
+
+        #[test]
+        fn timeout() {
+            let (tx_a, rx_a) = mpsc::channel();
+            let (tx_b, rx_b) = mpsc::channel();
+            let mut zipped = zip_eq_receivers_timeout(rx_a, rx_b, Duration::from_millis(1));
+            assert_eq!(zipped.next(), Some(Err(RecvZipError::Timeout(Side::Left))));
+
+            tx_b.send(1).unwrap();
+            assert_eq!(zipped.next(), Some(Err(RecvZipError::Timeout(Side::Left))));
+            tx_a.send(2).unwrap();
+            assert_eq!(zipped.next(), Some(Ok((2, 1))));
+
+            drop((tx_a, tx_b));
+            assert_eq!(zipped.next(), None);
+            assert_eq!(zipped.next(), None);
+        }
+
+        #[test]
+        fn timeout_length_mismatch() {
+            let (tx_a, rx_a) = mpsc::channel::<i32>();
+            let (tx_b, rx_b) = mpsc::channel();
+            tx_b.send(1).unwrap();
+            drop(tx_a);
+
+            let mut zipped = zip_eq_receivers_timeout(rx_a, rx_b, Duration::from_secs(60));
+            assert_eq!(
+                zipped.next(),
+                Some(Err(RecvZipError::Length(LengthMismatch::at(
+                    0,
+                    Side::Right
+                ))))
+            );
+            assert_eq!(zipped.next(), None);
+            drop(tx_b);
+        }
+    }
+
+    mod io {
+        use super::*;
+        use std::io;
+
+        fn mismatch(err: &io::Error) -> &ReadMismatch {
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+            err.get_ref().unwrap().downcast_ref().unwrap()
+        }
+
+        #[test]
+        fn lines() {
+            let a = "a\nb\r\n".as_bytes();
+            let b = "c\nd".as_bytes();
+            let lines: io::Result<Vec<_>> = zip_eq_lines(a, b).collect();
+            assert_eq!(
+                lines.unwrap(),
+                [("a".into(), "c".into()), ("b".into(), "d".into())]
+            );
+        }
+
+        #[test]
+        fn lines_fail() {
+            let a = "a\n".as_bytes();
+            let b = "c\nd\ne\n".as_bytes();
+            let mut zipped = zip_eq_lines(a, b);
+            assert!(zipped.next().unwrap().is_ok());
+
+            let err = zipped.next().unwrap().unwrap_err();
+            let mismatch = mismatch(&err);
+            assert_eq!(mismatch.line(), Some(2));
+            assert_eq!(mismatch.mismatch(), LengthMismatch::at(1, Side::Right));
+            assert_eq!(
+                err.to_string(),
+                "left input ended before line 2 but right input continues"
+            );
+            assert!(zipped.next().is_none());
+        }
+
+        #[test]
+        fn lines_read_error() {
+            let a = [b'a', b'\n', 0xff, b'\n'].as_slice();
+            let b = "c\nd\n".as_bytes();
+            let mut zipped = zip_eq_lines(a, b);
+            assert!(zipped.next().unwrap().is_ok());
+            assert_eq!(
+                zipped.next().unwrap().unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+            assert!(zipped.next().is_none());
+        }
+
+        #[test]
+        fn bytes() {
+            let a = [1, 2].as_slice();
+            let b = [3, 4].as_slice();
+            let bytes: io::Result<Vec<_>> = zip_eq_bytes(a, b).collect();
+            assert_eq!(bytes.unwrap(), [(1, 3), (2, 4)]);
+        }
+
+        #[test]
+        fn bytes_fail() {
+            let a = [1, 2, 3].as_slice();
+            let b = [3, 4].as_slice();
+            let err = zip_eq_bytes(a, b).last().unwrap().unwrap_err();
+            let mismatch = mismatch(&err);
+            assert_eq!(mismatch.line(), None);
+            assert_eq!(
+                err.to_string(),
+                "right input ended at byte offset 2 but left input continues"
+            );
+        }
+    }
+
+    mod nary {
+        use super::*;
+
+        #[test]
+        fn basic() {
+            let rows: Vec<_> = zip_eq_all([[1, 2], [3, 4], [5, 6]]).collect();
+            assert_eq!(rows, [[1, 3, 5], [2, 4, 6]]);
+        }
+
+        #[test]
+        fn empty() {
+            let mut rows = zip_eq_all(Vec::<Vec<i32>>::new());
+            assert_eq!(rows.len(), 0);
+            assert_eq!(rows.next(), None);
+        }
+
+        #[test]
+        fn len() {
+            let mut rows = zip_eq_all([vec![1, 2, 3], vec![4, 5, 6]]);
+            assert_eq!(rows.len(), 3);
+            rows.next();
+            assert_eq!(rows.len(), 2);
+        }
+
+        #[test]
+        #[should_panic]
+        fn basic_fail() {
+            zip_eq_all([vec![1, 2], vec![3, 4], vec![5]]).for_each(drop);
+        }
+
+        #[test]
+        fn try_next_fail() {
+            let mut rows = zip_eq_all([vec![1, 2], vec![3], vec![4, 5]]);
+            assert_eq!(rows.try_next(), Ok(Some(vec![1, 3, 4])));
+            let err = rows.try_next().unwrap_err();
+            assert_eq!(err, InputsMismatch::new(1, 1, 0));
+            assert_eq!(
+                err.to_string(),
+                "input 1 ended after 1 elements but input 0 has more"
+            );
+            assert_eq!(rows.try_next(), Ok(None));
+            assert_eq!(rows.next(), None);
+        }
+    }
+
+    mod columns {
+        use super::*;
+        use std::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        #[test]
+        fn pair_vec() {
+            let mut v = PairVec::new();
+            v.push((1, 'a'));
+            v.extend([(2, 'b'), (3, 'c')]);
+            v.insert(0, (0, 'z'));
+            assert_eq!(v.len(), 4);
+            assert_eq!(v.get(1), Some((&1, &'a')));
+            assert_eq!(v.get(4), None);
+            assert_eq!(v.swap_remove(0), (0, 'z'));
+            assert_eq!(v.remove(0), (3, 'c'));
+            assert_eq!(v.pop(), Some((2, 'b')));
+            assert_eq!(v.into_vecs(), (vec![1], vec!['a']));
+        }
+
+        #[test]
+        fn pair_vec_iter() {
+            let mut v: PairVec<_, _> = (0..4).map(|i| (i, i * 10)).collect();
+            for (a, b) in &mut v {
+                *b += *a;
+            }
+            assert_eq!(v.iter().len(), 4);
+            assert_eq!(v.iter().next_back(), Some((&3, &33)));
+            let pairs: Vec<_> = v.into_iter().collect();
+            assert_eq!(pairs, [(0, 0), (1, 11), (2, 22), (3, 33)]);
+        }
+
+        #[test]
+        fn from_vecs() {
+            assert_eq!(
+                PairVec::from_vecs(vec![1, 2], vec![3]),
+                Err(LengthMismatch::new(2, 1))
+            );
+            assert_eq!(
+                ColumnVec3::from_vecs(vec![1, 2], vec![3], vec![4, 5]),
+                Err(InputsMismatch::new(1, 1, 0))
+            );
+            let v = ColumnVec4::from_vecs(vec![1], vec![2], vec![3], vec![4]).unwrap();
+            assert_eq!(v.iter().collect::<Vec<_>>(), [(&1, &2, &3, &4)]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn insert_out_of_bounds() {
+            let mut v = PairVec::from_vecs(vec![1], vec![2]).unwrap();
+            v.insert(2, (3, 4));
+        }
+
+        #[test]
+        fn truncate() {
+            let mut v = ColumnVec3::new();
+            v.extend((0..5).map(|i| (i, i.to_string(), vec![i])));
+            v.truncate(2);
+            assert_eq!(v.len(), 2);
+            assert_eq!(v.as_slices().1, ["0", "1"]);
+            v.clear();
+            assert!(v.is_empty());
+        }
+
+        #[test]
+        fn truncate_panic() {
+            struct PanicOnDrop<'a>(&'a Cell<usize>);
+            impl Drop for PanicOnDrop<'_> {
+                fn drop(&mut self) {
+                    self.0.set(self.0.get() + 1);
+                    if self.0.get() == 1 {
+                        panic!();
+                    }
+                }
+            }
+
+            let drops = Cell::new(0);
+            let mut v = PairVec::new();
+            for i in 0..3 {
+                v.push((PanicOnDrop(&drops), i));
+            }
+            assert!(catch_unwind(AssertUnwindSafe(|| v.truncate(1))).is_err());
+            assert_eq!(v.as_slices().1, [0]);
+            assert_eq!(v.iter().count(), 1);
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use super::*;
+
+        #[derive(ZipEqRows, Default)]
+        struct Batch {
+            ids: Vec<u64>,
+            labels: Vec<&'static str>,
+        }
+
+        #[derive(ZipEqRows)]
+        struct Generic<T: Clone> {
+            values: Vec<T>,
+            weights: Vec<f64>,
+        }
+
+        // Implements neither `Debug` nor `Clone`.
+        struct Opaque(u8);
+
+        #[derive(ZipEqRows)]
+        struct Opaques<T> {
+            items: Vec<Opaque>,
+            values: Vec<T>,
+        }
+
+        #[test]
+        fn rows() {
+            let mut batch = Batch::default();
+            batch.push_row(1, "a");
+            batch.push_row(2, "b");
+            assert_eq!(batch.validate_lengths(), Ok(()));
+            for row in batch.rows_mut() {
+                *row.ids *= 10;
+            }
+            let rows: Vec<_> = batch.rows().map(|row| (*row.ids, *row.labels)).collect();
+            assert_eq!(rows, [(10, "a"), (20, "b")]);
+            assert_eq!(batch.rows().rev().len(), 2);
+        }
+
+        #[test]
+        fn generic() {
+            let g = Generic {
+                values: vec!['x', 'y'],
+                weights: vec![0.5, 1.5],
+            };
+            let row = g.rows().last().unwrap();
+            assert_eq!((*row.values, *row.weights), ('y', 1.5));
+        }
+
+        #[test]
+        fn opaque() {
+            let opaques = Opaques {
+                items: vec![Opaque(1), Opaque(2)],
+                values: vec![Opaque(3), Opaque(4)],
+            };
+            let row = opaques.rows().next().unwrap();
+            let copy = row;
+            assert_eq!((row.items.0, copy.values.0), (1, 3));
+        }
+
+        #[test]
+        fn debug() {
+            let mut batch = Batch::default();
+            batch.push_row(1, "a");
+            let row = batch.rows().next().unwrap();
+            assert_eq!(format!("{row:?}"), r#"BatchRow { ids: 1, labels: "a" }"#);
+            let row = batch.rows_mut().next().unwrap();
+            assert_eq!(format!("{row:?}"), r#"BatchRowMut { ids: 1, labels: "a" }"#);
+        }
+
+        #[test]
+        #[should_panic = "Batch: input 0 ended after 1 elements but input 1 has more"]
+        fn rows_fail() {
+            let batch = Batch {
+                ids: vec![1],
+                labels: vec!["a", "b"],
+            };
+            assert_eq!(batch.validate_lengths(), Err(InputsMismatch::new(1, 0, 1)));
+            batch.rows().for_each(drop);
+        }
+    }
+
+    mod unzip {
+        use super::*;
+        use std::collections::VecDeque;
+
+        #[test]
+        fn unzip() {
+            let (a, b): (Vec<_>, VecDeque<_>) = unzip_eq([1, 2, 3].zip_eq_eager([4, 5, 6]));
+            assert_eq!(a, [1, 2, 3]);
+            assert_eq!(b, [4, 5, 6]);
+        }
+
+        #[test]
+        fn unzip_six() {
+            let (a, b, c, d, e, f): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, String) =
+                unzip_eq((0..2).map(|i| (i, i + 1, i + 2, i + 3, i + 4, 'x')));
+            assert_eq!(
+                (a, b, c, d, e, f.as_str()),
+                (
+                    vec![0, 1],
+                    vec![1, 2],
+                    vec![2, 3],
+                    vec![3, 4],
+                    vec![4, 5],
+                    "xx"
+                )
+            );
+        }
+
+        #[test]
+        fn extend_reserves_exactly() {
+            let mut a = Vec::<i32>::new();
+            let mut b = Vec::<i32>::new();
+            extend_pairs(&mut a, &mut b, (0..100).map(|i| (i, -i)));
+            assert_eq!((a.len(), b.len()), (100, 100));
+            assert_eq!((a.capacity(), b.capacity()), (100, 100));
+        }
+    }
+
+    mod in_place {
+        use super::*;
+
+        #[test]
+        fn reuses_left_buffer() {
+            let a: Vec<u64> = (0..100).collect();
+            let ptr = a.as_ptr();
+            let b: Vec<u8> = (0..100).collect();
+            let c: Vec<i64> = a
+                .zip_eq_eager(b)
+                .map(|(a, b)| a as i64 - b as i64)
+                .collect();
+            assert_eq!(c.as_ptr() as *const u64, ptr);
+            assert!(c.iter().all(|&c| c == 0));
+        }
+
+        #[test]
+        fn partial() {
+            let a = vec![String::from("a"), String::from("b"), String::from("c")];
+            let ptr = a.as_ptr();
+            let c: Vec<String> = a
+                .zip_eq_eager([1, 2, 3])
+                .filter(|&(_, b)| b != 2)
+                .map(|(a, b)| a.repeat(b))
+                .collect();
+            assert_eq!(c, ["a", "ccc"]);
+            assert_eq!(c.as_ptr(), ptr);
+        }
+    }
+
+    mod permute {
+        use super::*;
+
+        #[test]
+        fn permutation() {
+            let mut a = vec![10, 20, 30, 40, 50];
+            let mut b = ['a', 'b', 'c', 'd', 'e'];
+            apply_permutation(&[4, 0, 3, 1, 2], (&mut a, &mut b));
+            assert_eq!(a, [50, 10, 40, 20, 30]);
+            assert_eq!(b, ['e', 'a', 'd', 'b', 'c']);
+        }
+
+        #[test]
+        #[should_panic = "not a permutation"]
+        fn not_a_permutation() {
+            let mut a = [1, 2, 3];
+            apply_permutation(&[1, 0, 0], &mut a);
+        }
+
+        #[test]
+        #[should_panic]
+        fn permutation_different_len() {
+            let mut a = [1, 2, 3];
+            let mut b = [1, 2];
+            apply_permutation(&[0, 1, 2], (&mut a, &mut b));
+        }
+
+        #[test]
+        fn sort_stable() {
+            let mut keys = [2, 1, 2, 1];
+            let mut values: Vec<_> = (0..4).collect();
+            co_sort(&mut keys, &mut values);
+            assert_eq!(keys, [1, 1, 2, 2]);
+            assert_eq!(values, [1, 3, 0, 2]);
+
+            co_sort_by(&mut keys, &mut values, |a, b| b.cmp(a));
+            assert_eq!(values, [0, 2, 1, 3]);
+        }
+
+        #[test]
+        fn sort_unstable() {
+            let mut keys = vec![5, 3, 9, 1];
+            let mut values = ['e', 'c', 'i', 'a'];
+            let mut other = [5.0, 3.0, 9.0, 1.0];
+            co_sort_unstable_by_key(&mut keys, (&mut values, &mut other), |&k| {
+                core::cmp::Reverse(k)
+            });
+            assert_eq!(keys, [9, 5, 3, 1]);
+            assert_eq!(values, ['i', 'e', 'c', 'a']);
+            assert_eq!(other, [9.0, 5.0, 3.0, 1.0]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn sort_different_len() {
+            let mut keys = [2, 1];
+            let mut values = [1];
+            co_sort_unstable(&mut keys, &mut values);
+        }
+
+        #[test]
+        fn shuffle() {
+            let mut a: Vec<_> = (0..10).collect();
+            let mut b: Vec<_> = (0..10).map(|i| i * 2).collect();
+            // Always picks the first index, which rotates the slices.
+            co_shuffle((&mut a, &mut b), &mut |_| 0);
+            assert_eq!(a, [1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
+            assert!(a.iter().zip_eq_eager(&b).all(|(a, b)| 2 * a == *b));
+        }
+    }
+
+    mod vec_pairs {
+        use super::*;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        #[test]
+        fn retain() {
+            let mut a: Vec<_> = (0..10).collect();
+            let mut b: Vec<_> = (0..10).map(|i| i.to_string()).collect();
+            retain_pairs(&mut a, &mut b, |a, b| a % 3 != 0 && b != "5");
+            assert_eq!(a, [1, 2, 4, 7, 8]);
+            assert_eq!(b, ["1", "2", "4", "7", "8"]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn retain_different_len() {
+            retain_pairs(&mut vec![1, 2], &mut vec![1], |_, _| true);
+        }
+
+        #[test]
+        fn retain_panic() {
+            let rc = Rc::new(());
+            let mut a: Vec<_> = (0..6).collect();
+            let mut b: Vec<_> = (0..6).map(|_| rc.clone()).collect();
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                retain_pairs(&mut a, &mut b, |&a, _| {
+                    assert!(a != 3);
+                    a % 2 == 0
+                })
+            }));
+            assert!(result.is_err());
+            assert_eq!(a, [0, 2, 3, 4, 5]);
+            assert_eq!(b.len(), 5);
+            assert_eq!(Rc::strong_count(&rc), 6);
+        }
+
+        #[test]
+        fn dedup() {
+            let mut a = vec![1, 1, 1, 2, 3, 3];
+            let mut b = vec!['a', 'b', 'c', 'd', 'e', 'f'];
+            dedup_pairs_by(&mut a, &mut b, |(a, _), (last, _)| a == last);
+            assert_eq!(a, [1, 2, 3]);
+            assert_eq!(b, ['a', 'd', 'e']);
+        }
+
+        #[test]
+        fn drain() {
+            let mut a: Vec<_> = (0..5).collect();
+            let mut b: Vec<_> = (5..10).collect();
+            let mut drained = drain_pairs(&mut a, &mut b, 1..);
+            assert_eq!(drained.next_back(), Some((4, 9)));
+            assert_eq!(drained.len(), 3);
+            drop(drained);
+            assert_eq!((a, b), (vec![0], vec![5]));
+        }
+
+        #[test]
+        fn drain_stateful_range() {
+            use core::cell::Cell;
+            use core::ops::{Bound, RangeBounds};
+
+            static ENDS: [usize; 4] = [1, 2, 3, 4];
+            // Returns a different range on each call.
+            struct Growing(Cell<usize>);
+            impl RangeBounds<usize> for Growing {
+                fn start_bound(&self) -> Bound<&usize> {
+                    Bound::Unbounded
+                }
+                fn end_bound(&self) -> Bound<&usize> {
+                    self.0.set(self.0.get() + 1);
+                    Bound::Excluded(&ENDS[self.0.get() % 4])
+                }
+            }
+            let mut a = vec![1, 2, 3, 4];
+            let mut b = vec![5, 6, 7, 8];
+            let drained = drain_pairs(&mut a, &mut b, Growing(Cell::new(0)));
+            let len = drained.len();
+            assert_eq!(drained.count(), len);
+            assert_eq!(a.len(), b.len());
+            assert_eq!(a.len(), 4 - len);
+        }
+
+        #[test]
+        fn drain_leaked() {
+            let mut a = vec![1, 2, 3];
+            let mut b = vec![4, 5, 6];
+            core::mem::forget(drain_pairs(&mut a, &mut b, 1..2));
+            assert_eq!(a.len(), b.len());
+        }
+
+        #[test]
+        fn truncate() {
+            let mut a = vec![String::new(); 4];
+            let mut b = vec![0; 4];
+            truncate_pairs(&mut a, &mut b, 5);
+            assert_eq!(a.len(), 4);
+            truncate_pairs(&mut a, &mut b, 1);
+            assert_eq!((a.len(), b.len()), (1, 1));
+        }
+    }
+
+    mod num {
+        use crate::num::*;
+
+        #[test]
+        fn kernels() {
+            let a = [1, 2, 3];
+            let b = [4, 6, 8];
+            let mut out = [0; 3];
+            assert_eq!(dot(&a, &b), 40);
+            add_into(&mut out, &a, &b);
+            assert_eq!(out, [5, 8, 11]);
+            mul_into(&mut out, &a, &b);
+            assert_eq!(out, [4, 12, 24]);
+            assert_eq!(squared_distance(&a, &b), 9 + 16 + 25);
+            assert_eq!(l1_distance(&b, &a), 12);
+        }
+
+        #[test]
+        fn unsigned_distance() {
+            let a = [1_u8, 9];
+            let b = [4_u8, 5];
+            assert_eq!(l1_distance(&a, &b), 7);
+            assert_eq!(squared_distance(&a, &b), 25);
+        }
+
+        #[test]
+        fn signed_abs_diff() {
+            assert_eq!(Scalar::abs_diff(-100_i8, 27), 127);
+            assert_eq!(Scalar::abs_diff(i8::MIN, -1), 127);
+            assert_eq!(l1_distance(&[i32::MIN + 1], &[0]), i32::MAX);
+        }
+
+        #[test]
+        #[cfg(debug_assertions)]
+        #[should_panic = "overflow"]
+        fn signed_abs_diff_overflow() {
+            Scalar::abs_diff(i8::MIN, 0);
+        }
+
+        #[test]
+        fn float_distances() {
+            assert_eq!(l2_distance(&[0.0, 3.0], &[4.0, 0.0]), 5.0);
+            assert!(cosine_distance(&[1.0_f32, 1.0], &[2.0, 2.0]).abs() < 1e-6);
+            assert!(cosine_distance(&[0.0_f64, 0.0], &[2.0, 2.0]).is_nan());
+        }
+
+        #[test]
+        fn pairwise() {
+            let a: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+            let b = vec![0.5; 1000];
+            assert_eq!(dot_pairwise(&a, &b), dot(&a, &b));
+            assert_eq!(dot_compensated(&a, &b), 249750.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn pairwise_fail() {
+            dot_pairwise(&[1.0; 200], &[1.0; 199]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn axpy_fail() {
+            axpy(1, &[1, 2], &mut [3]);
+        }
+    }
+
+    mod diff {
+        use super::*;
+
+        #[test]
+        fn values() {
+            assert_eq!(first_difference(Vec::<u8>::new(), [0_u8; 0]), None);
+            assert_eq!(first_difference("abc".chars(), "abc".chars()), None);
+            let diff = first_difference(["a", "b"], ["a", "c"]).unwrap();
+            assert_eq!(diff.index(), 1);
+            assert_eq!(
+                diff.to_string(),
+                r#"elements at index 1 differ: left is "b" but right is "c""#
+            );
+        }
+
+        #[test]
+        fn lengths() {
+            let diff = first_difference(0..5, 0..2).unwrap();
+            assert_eq!(
+                diff,
+                Difference::LengthMismatch {
+                    index: 2,
+                    longer_side: Side::Left,
+                    extra: 3,
+                }
+            );
+            assert_eq!(
+                diff.to_string(),
+                "right input ended after 2 elements but left input has 3 more"
+            );
+            // A value mismatch before the end wins.
+            assert_eq!(
+                first_difference([1, 2], [0]).unwrap(),
+                Difference::ValueMismatch {
+                    index: 0,
+                    left: 1,
+                    right: 0,
+                }
+            );
+        }
+
+        #[test]
+        fn by() {
+            let words = ["Foo", "BAR"];
+            let lower = ["foo", "bar"];
+            assert_eq!(
+                first_difference_by(words, lower, |a, b| a.eq_ignore_ascii_case(b)),
+                None
+            );
+            let diff = first_difference_by(words, [3, 4], |a, &b| a.len() == b).unwrap();
+            assert_eq!(diff.index(), 1);
+        }
+    }
+
+    mod assert {
+        use crate::{assert_iter_eq, assert_same_len, debug_assert_same_len};
+        use std::collections::HashMap;
+        use std::panic::catch_unwind;
+
+        fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+            let payload = catch_unwind(f).unwrap_err();
+            payload.downcast::<String>().map(|msg| *msg).unwrap()
+        }
+
+        #[test]
+        fn same_len() {
+            let v = vec![1, 2, 3];
+            let map: HashMap<_, _> = [(1, 'a'), (2, 'b'), (3, 'c')].into();
+            assert_same_len!(v, [4, 5, 6], map, "abc".bytes().rev(), 0..3);
+            debug_assert_same_len!(&v[..], v.iter().map(|x| x + 1), &map,);
+            assert_eq!(v.len(), 3);
+        }
+
+        #[test]
+        fn same_len_message() {
+            let ids = vec![1, 2, 3];
+            let msg = panic_message(|| assert_same_len!(ids, ids[1..]));
+            assert_eq!(
+                msg,
+                "assertion failed: `assert_same_len!(ids, ids[1..])`\n      ids: 3\n ids[1..]: 2"
+            );
+        }
+
+        #[test]
+        fn iter_eq() {
+            assert_iter_eq!(Vec::<u8>::new(), [0_u8; 0]);
+            assert_iter_eq!("abc".chars(), ['a', 'b', 'c'], "chars of {:?}", "abc");
+        }
+
+        #[test]
+        fn iter_eq_value_message() {
+            let msg = panic_message(|| assert_iter_eq!([1, 2, 3], [1, 5, 3], "step {}", 2));
+            assert_eq!(
+                msg,
+                "assertion failed: `left == right`: elements at index 1 differ: left is 2 but right \
+                 is 5: step 2\n  left: `[1, 2, 3]` (3 elements)\n right: `[1, 5, 3]` (3 elements)\n   \
+                 0: 1 | 1\n > 1: 2 | 5\n   2: 3 | 3"
+            );
+        }
+
+        #[test]
+        fn iter_eq_length_message() {
+            let msg = panic_message(|| assert_iter_eq!(0..12, 0..9));
+            assert_eq!(
+                msg,
+                "assertion failed: `left == right`: right input ended after 9 elements but left \
+                 input has 3 more\n  left: `0..12` (12 elements)\n right: `0..9` (9 elements)\n    \
+                  6: 6 | 6\n    7: 7 | 7\n    8: 8 | 8\n >  9: 9 | (end)\n   10: 10 | (end)\n   \
+                 11: 11 | (end)"
+            );
+        }
+    }
+
+    mod for_zip {
+        use crate::for_zip_eq;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        #[test]
+        fn flat_bindings() {
+            let a = [1, 2, 3];
+            let b = vec![4, 5, 6];
+            let c = [7, 8, 9];
+            let mut out = [0; 3];
+            for_zip_eq!((o, &a, mut b, c,) in (&mut out, &a, b, c.iter().copied(),) {
+                b *= 2;
+                *o = a + b + c;
+            });
+            assert_eq!(out, [16, 20, 24]);
+        }
+
+        #[test]
+        fn break_continue() {
+            let mut seen = Vec::new();
+            for_zip_eq!((a, b) in (0..10, 10..20) {
+                if a % 2 == 0 {
+                    continue;
+                }
+                if a > 6 {
+                    break;
+                }
+                seen.push(b);
+            });
+            assert_eq!(seen, [11, 13, 15]);
+
+            let mut count = 0;
+            for_zip_eq!('outer: (a, b) in (["ab", "cd"], [1, 2]) {
+                for c in a.chars() {
+                    if c == 'c' {
+                        continue 'outer;
+                    }
+                    count += b;
+                }
+            });
+            assert_eq!(count, 2);
+        }
+
+        #[test]
+        fn eager_when_possible() {
+            let mut count = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                for_zip_eq!((_, _) in (0..3, [1, 2]) {
+                    count += 1;
+                });
+            }));
+            assert!(result.is_err());
+            assert_eq!(count, 0);
+        }
+
+        #[test]
+        fn lazy_fallback() {
+            let mut count = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let evens = (0..6).filter(|x| x % 2 == 0);
+                for_zip_eq!((_, _, _) in (evens, [1, 2], 0..2) {
+                    count += 1;
+                });
+            }));
+            assert!(result.is_err());
+            assert_eq!(count, 2);
+
+            let mut sum = 0;
+            for_zip_eq!((a, b) in ((0..6).filter(|x| x % 2 == 0), 1..4) {
+                sum += a * b;
+            });
+            assert_eq!(sum, 16);
+        }
+
+        #[test]
+        fn lazy_left_shorter() {
+            let mut count = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                for_zip_eq!((_a, _b) in ((0..6).filter(|x| x % 2 == 0), 0..5) {
+                    count += 1;
+                });
+            }));
+            assert!(result.is_err());
+            assert_eq!(count, 3);
+        }
+    }
+
+    mod label {
+        use super::*;
+        use std::panic::catch_unwind;
+
+        fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+            let payload = catch_unwind(f).unwrap_err();
+            match payload.downcast::<String>() {
+                Ok(msg) => *msg,
+                Err(payload) => payload.downcast_ref::<&str>().unwrap().to_string(),
+            }
+        }
+
+        #[test]
+        fn lazy_left_shorter() {
+            assert_eq!(
+                panic_message(|| {
+                    let a = [1].label("a");
+                    a.zip_eq_lazy((0..3).filter(|_| true).label("b"))
+                        .for_each(drop);
+                }),
+                "ZipEq: a ended before b"
+            );
+        }
+
+        #[test]
+        fn eager() {
+            let zipped = zip_eq_labeled(("a", [1, 2]), ("b", vec![3, 4]));
+            assert_eq!(zipped.collect::<Vec<_>>(), [(1, 3), (2, 4)]);
+            assert_eq!(
+                panic_message(|| {
+                    [1, 2, 3].label("weights").zip_eq_eager(vec![1; 2]);
+                }),
+                "ZipEq: weights has 3 elements but right input has 2"
+            );
+            assert_eq!(
+                panic_message(|| {
+                    let w = [1, 2].label("w");
+                    let b = [3, 4].label("b");
+                    w.zip_eq_eager(b).zip_eq_eager([5].label("c"));
+                }),
+                "ZipEq: w has 2 elements but c has 1"
+            );
+        }
+
+        #[test]
+        fn lazy() {
+            let message = panic_message(|| {
+                let evens = (0..6).filter(|x| x % 2 == 0).label("evens");
+                evens.zip_eq_lazy([1, 2].label("pairs")).for_each(drop);
+            });
+            assert_eq!(message, "ZipEq: pairs ended before evens");
+            let message = panic_message(|| {
+                let mut zipped = [1].zip_eq_lazy([1, 2].label("pairs"));
+                zipped.next();
+                zipped.next();
+            });
+            assert_eq!(message, "ZipEq: left input ended before pairs");
+        }
+
+        #[test]
+        fn unlabeled() {
+            assert_eq!(
+                panic_message(|| {
+                    [1, 2].zip_eq_eager([1]);
+                }),
+                "ZipEq: Reached the end of one of the iterators before the other."
+            );
+        }
+
+        #[test]
+        fn errors() {
+            let a = [Ok::<_, ()>(1), Ok(2)].label("ids");
+            let b = [Ok::<_, ()>(3)];
+            let err = match try_zip_eq(a, b).last() {
+                Some(Err(ZipError::Length(err))) => err,
+                other => panic!("unexpected {other:?}"),
+            };
+            assert_eq!((err.left_label(), err.right_label()), (Some("ids"), None));
+            assert_eq!(
+                err.to_string(),
+                "right input ended after 1 elements but ids has more"
+            );
+        }
+    }
+
+    mod by_key {
+        use super::*;
+
+        #[test]
+        fn matching() {
+            let ids = [3, 5, 8];
+            let rows = vec![(3, 'a'), (5, 'b'), (8, 'c')];
+            let zipped = zip_eq_by_key(&ids, &rows, |&&id| id, |row| row.0);
+            assert_eq!(zipped.size_hint(), (3, Some(3)));
+            let letters: String = zipped.map(|(_, row)| row.1).collect();
+            assert_eq!(letters, "abc");
+        }
+
+        #[test]
+        fn key_mismatch() {
+            let mut zipped = zip_eq_by_key([1, 2, 3], [1, 4, 3], |&a| a, |&b| b);
+            assert_eq!(zipped.try_next(), Ok(Some((1, 1))));
+            let err = zipped.try_next().unwrap_err();
+            assert_eq!(err, KeyZipError::Key(KeyMismatch::new(1, 2, 4)));
+            assert_eq!(
+                err.to_string(),
+                "keys at index 1 differ: left input has 2 but right input has 4"
+            );
+            assert_eq!(zipped.try_next(), Ok(None));
+            assert_eq!(zipped.size_hint(), (0, Some(0)));
+        }
+
+        #[test]
+        fn length_mismatch() {
+            let a = ["x", "y"].label("a");
+            let mut zipped = zip_eq_by_key(a, ["x"], |s| s.len(), |s| s.len());
+            zipped.next();
+            let Err(KeyZipError::Length(err)) = zipped.try_next() else {
+                panic!("expected a length mismatch");
+            };
+            assert_eq!(
+                err,
+                LengthMismatch::at(1, Side::Left).with_labels(Some("a"), None)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics() {
+            zip_eq_by_key(["a"], ["b"], |s| s.to_string(), |s| s.to_string()).for_each(drop);
+        }
+    }
+
+    mod maps {
+        use super::*;
+        use std::collections::{BTreeMap, HashMap};
+
+        #[test]
+        fn sorted() {
+            let a = BTreeMap::from([(1, "one"), (2, "two")]);
+            let b = BTreeMap::from([(2, 2.0), (1, 1.0)]);
+            let zipped: Vec<_> = zip_eq_maps(&a, &b).collect();
+            assert_eq!(zipped, [(&1, &"one", &1.0), (&2, &"two", &2.0)]);
+            assert_eq!(zip_eq_maps(&a, &b).size_hint(), (2, Some(2)));
+        }
+
+        #[test]
+        fn unmatched() {
+            let a = [(1, ()), (2, ()), (4, ())];
+            let b = [(1, ()), (2, ()), (3, ()), (4, ())];
+            let mut zipped = zip_eq_maps(a.label("a"), b);
+            zipped.next();
+            zipped.next();
+            let err = zipped.try_next().unwrap_err();
+            assert_eq!(
+                err,
+                UnmatchedKey::new(3, Side::Right).with_labels(Some("a"), None)
+            );
+            assert_eq!(err.to_string(), "key 3 is in right input but not in a");
+
+            let mut zipped = zip_eq_maps([(1, 'a'), (5, 'b')], [(1, 'a')]);
+            zipped.next();
+            assert_eq!(zipped.try_next().unwrap_err().into_key(), 5);
+            assert_eq!(zipped.next(), None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn unmatched_panic() {
+            zip_eq_maps([("x", 1)], [("y", 1)]).for_each(drop);
+        }
+
+        #[test]
+        fn hash_maps() {
+            let a = HashMap::from([("x", 1), ("y", 2), ("z", 3)]);
+            let b = HashMap::from([("z", 30), ("x", 10), ("y", 20)]);
+            let zipped = zip_eq_hash_maps(&a, &b);
+            assert_eq!(zipped.len(), 3);
+            let mut products: Vec<_> = zipped.map(|(k, a, b)| (*k, a * b)).collect();
+            products.sort();
+            assert_eq!(products, [("x", 10), ("y", 40), ("z", 90)]);
+
+            let c = HashMap::from([("x", 0), ("y", 0), ("w", 0)]);
+            let mut zipped = zip_eq_hash_maps(&c, &a);
+            let err = loop {
+                match zipped.try_next() {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => panic!("expected an unmatched key"),
+                    Err(err) => break err,
+                }
+            };
+            assert_eq!((**err.key(), err.present_in()), ("w", Side::Left));
+            assert_eq!(zipped.len(), 0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn hash_map_lens() {
+            let a = HashMap::from([(1, 1)]);
+            zip_eq_hash_maps(&a, &HashMap::<i32, i32>::new());
+        }
+    }
+
+    mod map_eq {
+        use super::*;
+        use std::collections::{BTreeMap, HashMap};
+
+        #[test]
+        fn collect() {
+            let map: BTreeMap<_, _> = collect_map_eq(vec![3, 1, 2], ['c', 'a', 'b']).unwrap();
+            assert_eq!(
+                map.into_iter().collect::<Vec<_>>(),
+                [(1, 'a'), (2, 'b'), (3, 'c')]
+            );
+            let map: HashMap<_, _> =
+                collect_map_eq("abc".chars(), (0..).step_by(10).take(3)).unwrap();
+            assert_eq!((map.len(), map[&'c']), (3, 20));
+        }
+
+        #[test]
+        fn eager_length() {
+            let values = [1, 2].label("values");
+            let err = collect_map_eq::<HashMap<_, _>, _, _, _, _>(0..3, values).unwrap_err();
+            assert_eq!(
+                err,
+                MapEqError::Length(LengthMismatch::new(3, 2).with_labels(None, Some("values")))
+            );
+            assert_eq!(
+                err.to_string(),
+                "left input has 3 elements but values has 2"
+            );
+        }
+
+        #[test]
+        fn lazy_length() {
+            let keys = "ab".chars().label("keys");
+            let err = collect_map_eq::<BTreeMap<_, _>, _, _, _, _>(keys, [1, 2, 3]).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "keys ended after 2 elements but right input has more"
+            );
+        }
+
+        #[test]
+        fn unbounded_length() {
+            let err = collect_map_eq::<HashMap<_, _>, _, _, _, _>(0.., vec!["a", "b"]).unwrap_err();
+            assert_eq!(err, MapEqError::Length(LengthMismatch::at(2, Side::Left)));
+            let err = collect_map_eq::<BTreeMap<_, _>, _, _, _, _>(["a"], 0..).unwrap_err();
+            assert_eq!(err, MapEqError::Length(LengthMismatch::at(1, Side::Right)));
+        }
+
+        #[test]
+        fn duplicate() {
+            let keys = ["x", "y", "z", "y"];
+            let err = collect_map_eq::<HashMap<_, _>, _, _, _, _>(keys, 0..4).unwrap_err();
+            assert_eq!(err, MapEqError::Duplicate(DuplicateKey::new("y", 1, 3)));
+        }
+    }
+    mod nested {
+        use super::*;
+
+        #[test]
+        fn rows() {
+            let a = vec![vec![1, 2, 3], vec![], vec![4]];
+            let b = vec![vec!['a', 'b', 'c'], vec![], vec!['d']];
+            let rows: Vec<Vec<_>> = zip_eq_nested(&a, &b).map(Iterator::collect).collect();
+            assert_eq!(
+                rows,
+                [
+                    vec![(&1, &'a'), (&2, &'b'), (&3, &'c')],
+                    vec![],
+                    vec![(&4, &'d')]
+                ]
+            );
+        }
+
+        #[test]
+        fn row_mismatch() {
+            let a = vec![vec![0; 2], vec![0; 1], vec![0; 3], vec![0; 7]];
+            let b = vec![vec![0; 2], vec![0; 1], vec![0; 3], vec![0; 8]];
+            let mut zipped = zip_eq_nested(&a, &b);
+            for _ in 0..3 {
+                assert!(zipped.try_next().unwrap().is_some());
+            }
+            let err = zipped.try_next().unwrap_err();
+            assert_eq!(err, NestedMismatch::new(vec![3], 7, 8));
+            assert_eq!(err.to_string(), "row 3: 7 vs 8 elements");
+            assert!(zipped.try_next().unwrap().is_none());
+        }
+
+        #[test]
+        fn row_count() {
+            let a = vec![vec![1]; 2];
+            let b = vec![vec![1]; 5];
+            let mut zipped = zip_eq_nested(&b, &a);
+            assert_eq!(zipped.by_ref().take(2).count(), 2);
+            let err = zipped.try_next().unwrap_err();
+            assert_eq!(
+                (err.path(), err.left_len(), err.right_len()),
+                (&[][..], 5, 2)
+            );
+            assert_eq!(err.to_string(), "5 vs 2 rows");
+        }
+
+        #[test]
+        #[should_panic = "ZipEq: row 1, element 0: 2 vs 1 elements"]
+        fn deep_panic() {
+            let a = [vec![vec![1]], vec![vec![2, 3]]];
+            let b = [vec![vec![1]], vec![vec![2]]];
+            zip_eq_nested(&a, &b).flatten().for_each(drop);
+        }
+
+        #[test]
+        fn shape() {
+            let a = [[vec!["a"], vec!["b", "c"]], [vec![], vec!["d"]]];
+            let b = [[vec![1], vec![2, 3]], [vec![], vec![4]]];
+            assert_eq!(check_shape(&a, &b), Ok(()));
+            let c = [[vec![1], vec![2, 3]], [vec![0], vec![4]]];
+            assert_eq!(
+                check_shape(&a, &c),
+                Err(NestedMismatch::new(vec![1, 0], 0, 1))
+            );
+            let d: [[Vec<u8>; 2]; 1] = [[vec![], vec![]]];
+            assert_eq!(check_shape(&a, &d).unwrap_err().to_string(), "2 vs 1 rows");
+        }
+    }
+    mod transpose {
+        use super::*;
+
+        #[test]
+        fn eager() {
+            let rows = vec![vec!['a', 'b'], vec!['c', 'd'], vec!['e', 'f']];
+            let columns = transpose_eq(rows.clone()).unwrap();
+            assert_eq!(columns, [['a', 'c', 'e'], ['b', 'd', 'f']]);
+            assert_eq!(transpose_eq(columns).unwrap(), rows);
+            assert_eq!(transpose_eq(Vec::<Vec<u8>>::new()), Ok(vec![]));
+            assert_eq!(transpose_eq(vec![Vec::<u8>::new(); 3]), Ok(vec![]));
+        }
+
+        #[test]
+        fn eager_ragged() {
+            let rows = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9, 10], vec![11]];
+            assert_eq!(transpose_eq(rows), Err(RaggedError::new(2, 4, 3)));
+        }
+
+        #[test]
+        fn lazy() {
+            let columns: Vec<_> = transpose_eq_lazy(["ab".chars(), "cd".chars()]).collect();
+            assert_eq!(columns, [['a', 'c'], ['b', 'd']]);
+            let columns = transpose_eq_lazy([[0; 3]; 4]);
+            assert_eq!(columns.len(), 3);
+        }
+
+        #[test]
+        fn lazy_first_row_longer() {
+            let mut columns = transpose_eq_lazy([0..5, 0..5, 0..2, 0..1]);
+            assert_eq!(columns.try_next(), Ok(Some(vec![0; 4])));
+            let err = columns.try_next().unwrap_err();
+            assert_eq!(err, RaggedError::new(2, 2, 5));
+            assert_eq!(columns.try_next(), Ok(None));
+        }
+
+        #[test]
+        fn lazy_row_before_shortest_longer() {
+            let mut columns = transpose_eq_lazy([0..2, 0..4, 0..2, 0..1]);
+            assert_eq!(columns.try_next(), Ok(Some(vec![0; 4])));
+            assert_eq!(columns.try_next(), Err(RaggedError::new(1, 4, 2)));
+            let mut columns = transpose_eq_lazy([0..3, 0..3, 0..1]);
+            assert_eq!(columns.try_next(), Ok(Some(vec![0; 3])));
+            assert_eq!(columns.try_next(), Err(RaggedError::new(2, 1, 3)));
+        }
+
+        #[test]
+        #[should_panic = "ZipEq: row 2 has 4 elements but row 0 has 2"]
+        fn lazy_first_row_shorter() {
+            transpose_eq_lazy([vec![1, 2], vec![3, 4], vec![5, 6, 7, 8]]).for_each(drop);
+        }
+    }
 }
@@ -0,0 +1,186 @@
+use core::hash::{BuildHasher, Hash};
+use core::iter::TrustedLen;
+use std::collections::{btree_map, hash_map, BTreeMap, HashMap};
+
+use super::{DuplicateKey, LengthMismatch, MapEqError, ZipEq};
+
+/// Maps that can be built by [`collect_map_eq`], which needs to know where each key was first
+/// seen to report duplicates.
+///
+/// It is implemented for [`HashMap`] and [`BTreeMap`].
+pub trait MapEq<K, V>: Sized {
+    /// Map from each key to the index of its first occurrence and its value.
+    type Indexed;
+
+    /// Returns an empty indexed map, with room for `capacity` keys if the map supports it.
+    fn indexed_with_capacity(capacity: usize) -> Self::Indexed;
+
+    /// Inserts `key` and `value`, found at `index`, or returns `key` and the index of its first
+    /// occurrence if it is already in the map.
+    fn insert_indexed(
+        map: &mut Self::Indexed,
+        index: usize,
+        key: K,
+        value: V,
+    ) -> Result<(), (K, usize)>;
+
+    /// Drops the indices.
+    fn from_indexed(map: Self::Indexed) -> Self;
+}
+
+impl<K, V, S> MapEq<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Indexed = HashMap<K, (usize, V), S>;
+
+    fn indexed_with_capacity(capacity: usize) -> Self::Indexed {
+        HashMap::with_capacity_and_hasher(capacity, S::default())
+    }
+
+    fn insert_indexed(
+        map: &mut Self::Indexed,
+        index: usize,
+        key: K,
+        value: V,
+    ) -> Result<(), (K, usize)> {
+        match map.entry(key) {
+            // The keys are equal, and the map is dropped after the error.
+            hash_map::Entry::Occupied(entry) => {
+                let first = entry.get().0;
+                Err((entry.remove_entry().0, first))
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert((index, value));
+                Ok(())
+            }
+        }
+    }
+
+    fn from_indexed(map: Self::Indexed) -> Self {
+        let mut out = HashMap::with_capacity_and_hasher(map.len(), S::default());
+        out.extend(map.into_iter().map(|(key, (_, value))| (key, value)));
+        out
+    }
+}
+
+impl<K: Ord, V> MapEq<K, V> for BTreeMap<K, V> {
+    type Indexed = BTreeMap<K, (usize, V)>;
+
+    fn indexed_with_capacity(_: usize) -> Self::Indexed {
+        BTreeMap::new()
+    }
+
+    fn insert_indexed(
+        map: &mut Self::Indexed,
+        index: usize,
+        key: K,
+        value: V,
+    ) -> Result<(), (K, usize)> {
+        match map.entry(key) {
+            // The keys are equal, and the map is dropped after the error.
+            btree_map::Entry::Occupied(entry) => {
+                let first = entry.get().0;
+                Err((entry.remove_entry().0, first))
+            }
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert((index, value));
+                Ok(())
+            }
+        }
+    }
+
+    fn from_indexed(map: Self::Indexed) -> Self {
+        // The keys are sorted, so this builds the tree in bulk.
+        map.into_iter()
+            .map(|(key, (_, value))| (key, value))
+            .collect()
+    }
+}
+
+/// Returns the exact number of items of [`TrustedLen`] iterators, and `None` for the others.
+trait ExactLen {
+    fn exact_len(&self) -> Option<usize>;
+}
+
+impl<I: Iterator> ExactLen for I {
+    #[inline]
+    default fn exact_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<I: TrustedLen> ExactLen for I {
+    #[inline]
+    fn exact_len(&self) -> Option<usize> {
+        self.size_hint().1
+    }
+}
+
+/// Builds a map from separate sequences of keys and values, checking that they have the same
+/// length and that the keys are unique.
+///
+/// When both inputs are [`TrustedLen`], their lengths are compared before anything is inserted,
+/// and the map reserves room for all of them. Otherwise, they are zipped with
+/// [`ZipEq::zip_eq_lazy`] and a length mismatch is found at the end of the shorter one.
+///
+/// The map is first built with the index of each key, to report both occurrences of a
+/// duplicate, and the indices are dropped at the end.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::{BTreeMap, HashMap};
+/// use zip_eq::{collect_map_eq, MapEqError};
+///
+/// let names = ["ada", "bob"];
+/// let ages = vec![36, 41];
+/// let map: HashMap<_, _> = collect_map_eq(names, ages).unwrap();
+/// assert_eq!(map["bob"], 41);
+///
+/// let map: Result<BTreeMap<_, _>, _> = collect_map_eq([1, 2, 1], "abc".chars());
+/// let Err(MapEqError::Duplicate(dup)) = map else { panic!() };
+/// assert_eq!((*dup.key(), dup.first_index(), dup.index()), (1, 0, 2));
+/// assert_eq!(dup.to_string(), "key 1 appears at index 0 and at index 2");
+/// ```
+pub fn collect_map_eq<M, K, V, A, B>(keys: A, values: B) -> Result<M, MapEqError<K>>
+where
+    M: MapEq<K, V>,
+    A: IntoIterator<Item = K>,
+    B: IntoIterator<Item = V>,
+{
+    let mut zipped = keys.zip_eq_lazy(values);
+    let labels = zipped.labels();
+    let capacity = match (zipped.a.exact_len(), zipped.b.exact_len()) {
+        (Some(a), Some(b)) if a != b => {
+            let err = LengthMismatch::new(a, b).with_labels(labels[0], labels[1]);
+            return Err(MapEqError::Length(err));
+        }
+        (Some(len), Some(_)) => len,
+        // There can't be more pairs than elements in the shorter input.
+        _ => zipped.a.size_hint().0.min(zipped.b.size_hint().0),
+    };
+
+    let mut map = M::indexed_with_capacity(capacity);
+    let mut index = 0;
+    loop {
+        match zipped.try_next() {
+            Ok(Some((key, value))) => {
+                if let Err((key, first_index)) = M::insert_indexed(&mut map, index, key, value) {
+                    return Err(MapEqError::Duplicate(DuplicateKey::new(
+                        key,
+                        first_index,
+                        index,
+                    )));
+                }
+                index += 1;
+            }
+            Ok(None) => return Ok(M::from_indexed(map)),
+            Err(longer) => {
+                let err = LengthMismatch::at(index, longer).with_labels(labels[0], labels[1]);
+                return Err(MapEqError::Length(err));
+            }
+        }
+    }
+}
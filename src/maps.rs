@@ -0,0 +1,270 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use std::collections::{hash_map, HashMap};
+
+use super::label::InputLabel;
+use super::{LengthMismatch, Side, UnmatchedKey};
+
+/// Iterator that zips two iterators of `(key, value)` pairs sorted by key, checking that they
+/// have the same keys. It is created by [`zip_eq_maps`].
+#[derive(Debug, Clone)]
+pub struct ZipEqMaps<A, B> {
+    a: A,
+    b: B,
+    done: bool,
+}
+
+/// Returns an iterator that zips two maps, or iterators of `(key, value)` pairs sorted by key,
+/// checking during iteration that they have exactly the same keys.
+///
+/// It yields the key and both values for each key. Since the inputs are sorted, the first key
+/// that is in only one of them is found when it's reached, and is reported as an
+/// [`UnmatchedKey`].
+/// # Panics
+/// Iterating panics if a key is in only one of the inputs. Use [`ZipEqMaps::try_next`] to get
+/// an [`UnmatchedKey`] instead. The key that is reported is unspecified if the inputs aren't
+/// sorted.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// let prices = BTreeMap::from([("apple", 3), ("pear", 4)]);
+/// let stock = BTreeMap::from([("pear", 10), ("apple", 0)]);
+/// let value: Vec<_> = zip_eq::zip_eq_maps(&prices, &stock)
+///     .map(|(fruit, price, count)| (*fruit, price * count))
+///     .collect();
+///
+/// assert_eq!(value, [("apple", 0), ("pear", 40)]);
+/// ```
+///
+/// ```should_panic
+/// use std::collections::BTreeMap;
+///
+/// let prices = BTreeMap::from([("apple", 3), ("pear", 4)]);
+/// let stock = BTreeMap::from([("apple", 0), ("kiwi", 2), ("pear", 10)]);
+/// // panics with "ZipEq: key "kiwi" is in right input but not in left input"
+/// zip_eq::zip_eq_maps(&prices, &stock).for_each(drop);
+/// ```
+pub fn zip_eq_maps<A, B, K, V1, V2>(a: A, b: B) -> ZipEqMaps<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator<Item = (K, V1)>,
+    B: IntoIterator<Item = (K, V2)>,
+    K: Ord,
+{
+    ZipEqMaps {
+        a: a.into_iter(),
+        b: b.into_iter(),
+        done: false,
+    }
+}
+
+impl<A, B, K, V1, V2> ZipEqMaps<A, B>
+where
+    A: Iterator<Item = (K, V1)>,
+    B: Iterator<Item = (K, V2)>,
+    K: Ord,
+{
+    /// Returns the next key with both of its values, or the first key that is in only one of
+    /// the inputs. The iteration ends after the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::Side;
+    ///
+    /// let mut zipped = zip_eq::zip_eq_maps([(1, 'a'), (3, 'c')], [(1, "a"), (2, "b")]);
+    ///
+    /// assert_eq!(zipped.try_next(), Ok(Some((1, 'a', "a"))));
+    /// let err = zipped.try_next().unwrap_err();
+    /// assert_eq!((*err.key(), err.present_in()), (2, Side::Right));
+    /// assert_eq!(zipped.try_next(), Ok(None));
+    /// ```
+    pub fn try_next(&mut self) -> Result<Option<(K, V1, V2)>, UnmatchedKey<K>> {
+        if self.done {
+            return Ok(None);
+        }
+        let (key, present_in) = match (self.a.next(), self.b.next()) {
+            (Some((ka, va)), Some((kb, vb))) => match ka.cmp(&kb) {
+                Ordering::Equal => return Ok(Some((ka, va, vb))),
+                // The smaller key would have come first in the other input too.
+                Ordering::Less => (ka, Side::Left),
+                Ordering::Greater => (kb, Side::Right),
+            },
+            (None, None) => {
+                self.done = true;
+                return Ok(None);
+            }
+            (Some((ka, _)), None) => (ka, Side::Left),
+            (None, Some((kb, _))) => (kb, Side::Right),
+        };
+        self.done = true;
+        let err = UnmatchedKey::new(key, present_in);
+        Err(err.with_labels(self.a.input_label(), self.b.input_label()))
+    }
+
+    /// Returns the inputs, with the pairs that were already yielded consumed.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+#[cold]
+fn panic_unmatched_key<K: fmt::Debug>(err: UnmatchedKey<K>) -> ! {
+    panic!("ZipEq: {err}");
+}
+
+impl<A, B, K, V1, V2> Iterator for ZipEqMaps<A, B>
+where
+    A: Iterator<Item = (K, V1)>,
+    B: Iterator<Item = (K, V2)>,
+    K: Ord + fmt::Debug,
+{
+    type Item = (K, V1, V2);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(item) => item,
+            Err(err) => panic_unmatched_key(err),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        super::size_hint_impl(self.a.size_hint(), self.b.size_hint())
+    }
+}
+
+impl<A, B, K, V1, V2> FusedIterator for ZipEqMaps<A, B>
+where
+    A: Iterator<Item = (K, V1)>,
+    B: Iterator<Item = (K, V2)>,
+    K: Ord + fmt::Debug,
+{
+}
+
+/// Iterator that zips two `HashMap`s with the same keys, created by [`zip_eq_hash_maps`].
+#[derive(Debug, Clone)]
+pub struct ZipEqHashMaps<'a, 'b, K, V1, V2, S> {
+    iter: hash_map::Iter<'a, K, V1>,
+    other: &'b HashMap<K, V2, S>,
+    done: bool,
+}
+
+/// Returns an iterator that zips two `HashMap`s, after checking that they have the same number
+/// of keys. It yields the keys of `a` in its iteration order, with their values in both maps.
+///
+/// Each key of `a` is looked up in `b` during iteration. Since the maps have the same length,
+/// they have the same keys if all the lookups succeed.
+/// # Panics
+/// Panics if `a.len() != b.len()`. Iterating panics if a key of `a` isn't in `b`. Use
+/// [`ZipEqHashMaps::try_next`] to get an [`UnmatchedKey`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let prices = HashMap::from([("apple", 3), ("pear", 4)]);
+/// let stock = HashMap::from([("pear", 10), ("apple", 0)]);
+/// let total: i32 = zip_eq::zip_eq_hash_maps(&prices, &stock)
+///     .map(|(_, price, count)| price * count)
+///     .sum();
+///
+/// assert_eq!(total, 40);
+/// ```
+pub fn zip_eq_hash_maps<'a, 'b, K, V1, V2, S1, S2>(
+    a: &'a HashMap<K, V1, S1>,
+    b: &'b HashMap<K, V2, S2>,
+) -> ZipEqHashMaps<'a, 'b, K, V1, V2, S2>
+where
+    K: Eq + Hash,
+    S2: BuildHasher,
+{
+    if a.len() != b.len() {
+        panic_map_lens(a.len(), b.len());
+    }
+    ZipEqHashMaps {
+        iter: a.iter(),
+        other: b,
+        done: false,
+    }
+}
+
+#[cold]
+fn panic_map_lens(a: usize, b: usize) -> ! {
+    panic!("ZipEq: {}", LengthMismatch::new(a, b));
+}
+
+impl<'a, 'b, K, V1, V2, S> ZipEqHashMaps<'a, 'b, K, V1, V2, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Returns the next key with both of its values, or an error if the key isn't in the right
+    /// map. The iteration ends after the error.
+    pub fn try_next(&mut self) -> Result<Option<(&'a K, &'a V1, &'b V2)>, UnmatchedKey<&'a K>> {
+        if self.done {
+            return Ok(None);
+        }
+        let Some((key, a)) = self.iter.next() else {
+            self.done = true;
+            return Ok(None);
+        };
+        match self.other.get(key) {
+            Some(b) => Ok(Some((key, a, b))),
+            None => {
+                self.done = true;
+                Err(UnmatchedKey::new(key, Side::Left))
+            }
+        }
+    }
+}
+
+impl<'a, 'b, K, V1, V2, S> Iterator for ZipEqHashMaps<'a, 'b, K, V1, V2, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V1, &'b V2);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(item) => item,
+            Err(err) => panic_unmatched_key(err),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V1, V2, S> ExactSizeIterator for ZipEqHashMaps<'_, '_, K, V1, V2, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    S: BuildHasher,
+{
+    fn len(&self) -> usize {
+        if self.done {
+            0
+        } else {
+            self.iter.len()
+        }
+    }
+}
+
+impl<K, V1, V2, S> FusedIterator for ZipEqHashMaps<'_, '_, K, V1, V2, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    S: BuildHasher,
+{
+}
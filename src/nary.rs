@@ -0,0 +1,115 @@
+use core::iter::FusedIterator;
+
+use super::InputsMismatch;
+
+/// Iterator that zips any number of iterators of the same type, created by [`zip_eq_all`].
+///
+/// It yields a `Vec` holding the next item of every input, in the order of the inputs.
+#[derive(Debug, Clone)]
+pub struct ZipEqAll<I> {
+    pub(crate) iters: Vec<I>,
+    index: usize,
+    done: bool,
+}
+
+/// Returns an iterator that zips all the iterators of `iters`, checking that they have the same
+/// length during iteration.
+///
+/// If `iters` is empty, the returned iterator is empty.
+/// # Panics
+/// Iterating panics if one of the iterators ends before the others. Use
+/// [`ZipEqAll::try_next`] to get an [`InputsMismatch`] instead.
+///
+/// # Examples
+///
+/// ```
+/// let columns = [vec![1, 2], vec![3, 4], vec![5, 6]];
+/// let mut rows = zip_eq::zip_eq_all(columns);
+///
+/// assert_eq!(rows.next(), Some(vec![1, 3, 5]));
+/// assert_eq!(rows.next(), Some(vec![2, 4, 6]));
+/// assert_eq!(rows.next(), None);
+/// ```
+pub fn zip_eq_all<T: IntoIterator>(iters: impl IntoIterator<Item = T>) -> ZipEqAll<T::IntoIter> {
+    ZipEqAll {
+        iters: iters.into_iter().map(IntoIterator::into_iter).collect(),
+        index: 0,
+        done: false,
+    }
+}
+
+impl<I: Iterator> ZipEqAll<I> {
+    /// Returns the next items of all the inputs, or an error if some of the inputs ended before
+    /// the others. The iteration ends after the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut rows = zip_eq::zip_eq_all([vec![1, 2], vec![3]]);
+    ///
+    /// assert_eq!(rows.try_next(), Ok(Some(vec![1, 3])));
+    /// let err = rows.try_next().unwrap_err();
+    /// assert_eq!((err.index(), err.ended(), err.continued()), (1, 1, 0));
+    /// assert_eq!(rows.try_next(), Ok(None));
+    /// ```
+    pub fn try_next(&mut self) -> Result<Option<Vec<I::Item>>, InputsMismatch> {
+        if self.done || self.iters.is_empty() {
+            return Ok(None);
+        }
+        let items: Vec<_> = self.iters.iter_mut().map(Iterator::next).collect();
+        let ended = items.iter().position(Option::is_none);
+        let continued = items.iter().position(Option::is_some);
+        match (ended, continued) {
+            (None, _) => {
+                self.index += 1;
+                Ok(Some(items.into_iter().flatten().collect()))
+            }
+            (Some(_), None) => {
+                self.done = true;
+                Ok(None)
+            }
+            (Some(ended), Some(continued)) => {
+                self.done = true;
+                Err(InputsMismatch::new(self.index, ended, continued))
+            }
+        }
+    }
+
+    /// Returns the inputs, with the items that were already yielded consumed.
+    pub fn into_inner(self) -> Vec<I> {
+        self.iters
+    }
+}
+
+impl<I: Iterator> Iterator for ZipEqAll<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(items) => items,
+            Err(_) => super::panic_different_len(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        self.iters
+            .iter()
+            .map(Iterator::size_hint)
+            .reduce(super::size_hint_impl)
+            .unwrap_or((0, Some(0)))
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for ZipEqAll<I> {
+    fn len(&self) -> usize {
+        match self.iters.first() {
+            Some(iter) if !self.done => iter.len(),
+            _ => 0,
+        }
+    }
+}
+
+impl<I: Iterator> FusedIterator for ZipEqAll<I> {}
@@ -0,0 +1,229 @@
+use core::iter::FusedIterator;
+use core::ops::Deref;
+
+use super::{NestedMismatch, Side, ZipEq, ZipEqLazyCheck};
+
+/// Element types that end the recursion of [`Shape`].
+///
+/// It is implemented for the primitive types and strings. Implement it for other element types
+/// to compare containers of them.
+pub trait Leaf {}
+
+macro_rules! leaf {
+    ($($T:ty),+) => {$(
+        impl Leaf for $T {}
+    )+};
+}
+
+leaf!(i8, i16, i32, i64, i128, isize);
+leaf!(u8, u16, u32, u64, u128, usize);
+leaf!(f32, f64, bool, char, (), str, &str, String);
+
+/// Nested containers whose shape can be compared with one of `Rhs`, at any depth.
+///
+/// It is implemented for [`Leaf`] types, which have no shape, and for `Vec`s, slices and arrays
+/// whose elements implement it.
+pub trait Shape<Rhs: ?Sized> {
+    /// Returns the first difference between the shapes of `self` and `other`, in depth-first
+    /// order.
+    fn shape_mismatch(&self, other: &Rhs) -> Option<NestedMismatch>;
+}
+
+impl<T: Leaf + ?Sized, U: Leaf + ?Sized> Shape<U> for T {
+    #[inline]
+    fn shape_mismatch(&self, _: &U) -> Option<NestedMismatch> {
+        None
+    }
+}
+
+impl<T: Shape<U>, U> Shape<[U]> for [T] {
+    fn shape_mismatch(&self, other: &[U]) -> Option<NestedMismatch> {
+        if self.len() != other.len() {
+            return Some(NestedMismatch::new(Vec::new(), self.len(), other.len()));
+        }
+        // SAFETY: the slices have the same length
+        let zipped = unsafe { self.zip_eq_unchecked(other) };
+        zipped
+            .enumerate()
+            .find_map(|(i, (a, b))| Some(a.shape_mismatch(b)?.in_row(i)))
+    }
+}
+
+impl<T: Shape<U>, U> Shape<Vec<U>> for Vec<T> {
+    #[inline]
+    fn shape_mismatch(&self, other: &Vec<U>) -> Option<NestedMismatch> {
+        self[..].shape_mismatch(&other[..])
+    }
+}
+
+impl<T: Shape<U>, U, const N: usize, const M: usize> Shape<[U; M]> for [T; N] {
+    #[inline]
+    fn shape_mismatch(&self, other: &[U; M]) -> Option<NestedMismatch> {
+        self[..].shape_mismatch(&other[..])
+    }
+}
+
+/// Checks that `a` and `b` have the same shape, at every depth.
+///
+/// # Examples
+///
+/// ```
+/// let a = vec![vec![vec![1, 2], vec![3]], vec![]];
+/// let b = vec![vec![vec![0.5, 1.5], vec![2.5, 3.5]], vec![]];
+/// let err = zip_eq::check_shape(&a, &b).unwrap_err();
+///
+/// assert_eq!(err.path(), [0, 1]);
+/// assert_eq!(err.to_string(), "row 0, element 1: 1 vs 2 elements");
+/// ```
+pub fn check_shape<A: Shape<B> + ?Sized, B: ?Sized>(a: &A, b: &B) -> Result<(), NestedMismatch> {
+    match a.shape_mismatch(b) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Iterator that zips two sequences of rows, checking that every pair of rows has the same
+/// shape. It is created by [`zip_eq_nested`].
+#[derive(Debug, Clone)]
+pub struct ZipEqNested<A, B> {
+    zipped: ZipEqLazyCheck<A, B>,
+    index: usize,
+    done: bool,
+}
+
+/// Returns an iterator that zips the rows of `a` and `b`, checking that they have the same number
+/// of rows and that each pair of rows has the same shape, down to the innermost elements.
+///
+/// The rows are compared through [`Deref`], so they can be references to `Vec`s, slices or
+/// arrays, or owned `Vec`s. Each row is checked when it's reached, and is yielded as a
+/// [`ZipEqLazyCheck`] over the elements of both rows. Use [`Iterator::flatten`] to iterate over
+/// the pairs of elements directly.
+///
+/// # Panics
+/// Iterating panics if the shapes differ, with the path to the first difference, as in
+/// `row 3: 7 vs 8 elements`. Use [`ZipEqNested::try_next`] to get a [`NestedMismatch`]
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// let a = vec![vec![1, 2], vec![3]];
+/// let b = vec![vec![10, 20], vec![30]];
+/// let sums: Vec<i32> = zip_eq::zip_eq_nested(&a, &b).flatten().map(|(a, b)| a + b).collect();
+///
+/// assert_eq!(sums, [11, 22, 33]);
+/// ```
+///
+/// ```should_panic
+/// let a = vec![vec![1, 2], vec![3]];
+/// let b = vec![vec![10, 20], vec![30, 40]];
+/// // panics with "ZipEq: row 1: 1 vs 2 elements"
+/// zip_eq::zip_eq_nested(&a, &b).for_each(drop);
+/// ```
+pub fn zip_eq_nested<A, B>(a: A, b: B) -> ZipEqNested<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+    A::Item: Deref + IntoIterator,
+    B::Item: Deref + IntoIterator,
+    <A::Item as Deref>::Target: Shape<<B::Item as Deref>::Target>,
+{
+    ZipEqNested {
+        zipped: a.zip_eq_lazy(b),
+        index: 0,
+        done: false,
+    }
+}
+
+type Row<A, B> = ZipEqLazyCheck<
+    <<A as Iterator>::Item as IntoIterator>::IntoIter,
+    <<B as Iterator>::Item as IntoIterator>::IntoIter,
+>;
+
+impl<A, B> ZipEqNested<A, B>
+where
+    A: Iterator,
+    B: Iterator,
+    A::Item: Deref + IntoIterator,
+    B::Item: Deref + IntoIterator,
+    <A::Item as Deref>::Target: Shape<<B::Item as Deref>::Target>,
+{
+    /// Returns the next pair of rows, or the first difference between the shapes of the inputs.
+    /// The iteration ends after the error.
+    ///
+    /// If one of the inputs has fewer rows, the rest of the other one is consumed to count its
+    /// rows.
+    pub fn try_next(&mut self) -> Result<Option<Row<A, B>>, NestedMismatch> {
+        if self.done {
+            return Ok(None);
+        }
+        let (a, b) = match self.zipped.try_next() {
+            Ok(Some(rows)) => rows,
+            Ok(None) => {
+                self.done = true;
+                return Ok(None);
+            }
+            Err(longer) => {
+                self.done = true;
+                // The row that revealed the mismatch was already taken.
+                let longer_len = self.index
+                    + 1
+                    + match longer {
+                        Side::Left => self.zipped.a.by_ref().count(),
+                        Side::Right => self.zipped.b.by_ref().count(),
+                    };
+                let (left_len, right_len) = match longer {
+                    Side::Left => (longer_len, self.index),
+                    Side::Right => (self.index, longer_len),
+                };
+                return Err(NestedMismatch::new(Vec::new(), left_len, right_len));
+            }
+        };
+        if let Some(err) = (*a).shape_mismatch(&*b) {
+            self.done = true;
+            return Err(err.in_row(self.index));
+        }
+        self.index += 1;
+        Ok(Some(a.zip_eq_lazy(b)))
+    }
+}
+
+#[cold]
+fn panic_nested_mismatch(err: NestedMismatch) -> ! {
+    panic!("ZipEq: {err}");
+}
+
+impl<A, B> Iterator for ZipEqNested<A, B>
+where
+    A: Iterator,
+    B: Iterator,
+    A::Item: Deref + IntoIterator,
+    B::Item: Deref + IntoIterator,
+    <A::Item as Deref>::Target: Shape<<B::Item as Deref>::Target>,
+{
+    type Item = Row<A, B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(row) => row,
+            Err(err) => panic_nested_mismatch(err),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        self.zipped.size_hint()
+    }
+}
+
+impl<A, B> FusedIterator for ZipEqNested<A, B>
+where
+    A: Iterator,
+    B: Iterator,
+    A::Item: Deref + IntoIterator,
+    B::Item: Deref + IntoIterator,
+    <A::Item as Deref>::Target: Shape<<B::Item as Deref>::Target>,
+{
+}
@@ -0,0 +1,234 @@
+//! Numeric kernels over slices of the same length.
+//!
+//! Each kernel checks the lengths once, then iterates with [`ZipEq::zip_eq_eager`], so the inner
+//! loops have no bounds checks and can be vectorized by the compiler.
+//!
+//! # Panics
+//! All the kernels panic if their inputs don't have the same length.
+//!
+//! # Examples
+//!
+//! ```
+//! use zip_eq::num;
+//!
+//! let x = [1.0, 2.0, 3.0];
+//! let mut y = [1.0, 1.0, 1.0];
+//! num::axpy(2.0, &x, &mut y);
+//!
+//! assert_eq!(y, [3.0, 5.0, 7.0]);
+//! assert_eq!(num::dot(&x, &y), 34.0);
+//! assert_eq!(num::l1_distance(&x, &y), 9.0);
+//! ```
+
+use core::iter::TrustedLen;
+use core::ops::{Add, Div, Mul, Sub};
+
+use super::ZipEq;
+
+/// Number types supported by the kernels.
+pub trait Scalar:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// The additive identity.
+    const ZERO: Self;
+    /// The multiplicative identity.
+    const ONE: Self;
+
+    /// Returns the absolute value of the difference between `self` and `other`, without
+    /// overflowing for unsigned types.
+    ///
+    /// For signed integers, the result must fit in `Self`, as with `-`. It overflows otherwise,
+    /// as for `Scalar::abs_diff(i8::MIN, 0)`, which panics in debug builds.
+    fn abs_diff(self, other: Self) -> Self;
+}
+
+/// Floating point types, supported by the kernels that need a square root or compensated sums.
+pub trait Float: Scalar + Div<Output = Self> {
+    /// Returns the square root of `self`.
+    fn sqrt(self) -> Self;
+    /// Returns the absolute value of `self`.
+    fn abs(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($zero:literal, $one:literal, |$a:ident, $b:ident| $abs_diff:expr; $($T:ty),+) => {$(
+        impl Scalar for $T {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+
+            #[inline]
+            fn abs_diff(self, other: Self) -> Self {
+                let ($a, $b) = (self, other);
+                $abs_diff
+            }
+        }
+    )+};
+}
+
+scalar!(0, 1, |a, b| (a - b).abs(); i8, i16, i32, i64, i128, isize);
+scalar!(0, 1, |a, b| a.abs_diff(b); u8, u16, u32, u64, u128, usize);
+scalar!(0.0, 1.0, |a, b| (a - b).abs(); f32, f64);
+
+macro_rules! float {
+    ($($T:ty),+) => {$(
+        impl Float for $T {
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$T>::sqrt(self)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                <$T>::abs(self)
+            }
+        }
+    )+};
+}
+
+float!(f32, f64);
+
+/// Returns the dot product of `a` and `b`.
+pub fn dot<T: Scalar>(a: &[T], b: &[T]) -> T {
+    a.zip_eq_eager(b).fold(T::ZERO, |acc, (&a, &b)| acc + a * b)
+}
+
+/// Returns the dot product of the items of `a` and `b`, which must be [`TrustedLen`] iterators
+/// of the same length.
+///
+/// # Examples
+///
+/// ```
+/// let evens = (0..4).map(|i| 2 * i);
+/// assert_eq!(zip_eq::num::dot_iter(evens, [1, 1, 1, 1]), 12);
+/// ```
+pub fn dot_iter<T, A, B>(a: A, b: B) -> T
+where
+    T: Scalar,
+    A: IntoIterator<Item = T>,
+    A::IntoIter: TrustedLen + ExactSizeIterator,
+    B: IntoIterator<Item = T>,
+    B::IntoIter: TrustedLen + ExactSizeIterator,
+{
+    a.zip_eq_eager(b).fold(T::ZERO, |acc, (a, b)| acc + a * b)
+}
+
+/// Computes `y = alpha * x + y`.
+pub fn axpy<T: Scalar>(alpha: T, x: &[T], y: &mut [T]) {
+    y.iter_mut()
+        .zip_eq_eager(x)
+        .for_each(|(y, &x)| *y = alpha * x + *y);
+}
+
+/// Writes the element-wise sums of `a` and `b` into `out`.
+pub fn add_into<T: Scalar>(out: &mut [T], a: &[T], b: &[T]) {
+    out.iter_mut()
+        .zip_eq_eager(a)
+        .zip_eq_eager(b)
+        .for_each(|((out, &a), &b)| *out = a + b);
+}
+
+/// Writes the element-wise products of `a` and `b` into `out`.
+pub fn mul_into<T: Scalar>(out: &mut [T], a: &[T], b: &[T]) {
+    out.iter_mut()
+        .zip_eq_eager(a)
+        .zip_eq_eager(b)
+        .for_each(|((out, &a), &b)| *out = a * b);
+}
+
+/// Returns the squared Euclidean distance between `a` and `b`.
+pub fn squared_distance<T: Scalar>(a: &[T], b: &[T]) -> T {
+    a.zip_eq_eager(b).fold(T::ZERO, |acc, (&a, &b)| {
+        let d = a.abs_diff(b);
+        acc + d * d
+    })
+}
+
+/// Returns the Manhattan distance between `a` and `b`.
+pub fn l1_distance<T: Scalar>(a: &[T], b: &[T]) -> T {
+    a.zip_eq_eager(b)
+        .fold(T::ZERO, |acc, (&a, &b)| acc + a.abs_diff(b))
+}
+
+/// Returns the Euclidean distance between `a` and `b`.
+pub fn l2_distance<T: Float>(a: &[T], b: &[T]) -> T {
+    squared_distance(a, b).sqrt()
+}
+
+/// Returns the cosine distance between `a` and `b`, which is one minus the cosine of the angle
+/// between them.
+///
+/// The result is NaN if one of the inputs has a norm of zero.
+///
+/// # Examples
+///
+/// ```
+/// let d = zip_eq::num::cosine_distance(&[1.0, 0.0], &[0.0, 2.0]);
+/// assert_eq!(d, 1.0);
+/// ```
+pub fn cosine_distance<T: Float>(a: &[T], b: &[T]) -> T {
+    let (dot, a_norm, b_norm) = a
+        .zip_eq_eager(b)
+        .fold((T::ZERO, T::ZERO, T::ZERO), |(dot, aa, bb), (&a, &b)| {
+            (dot + a * b, aa + a * a, bb + b * b)
+        });
+    T::ONE - dot / (a_norm.sqrt() * b_norm.sqrt())
+}
+
+/// Length under which [`dot_pairwise`] sums the products sequentially.
+const PAIRWISE_BLOCK: usize = 128;
+
+/// # Safety
+/// `a` and `b` must have the same length.
+unsafe fn dot_pairwise_unchecked<T: Float>(a: &[T], b: &[T]) -> T {
+    if a.len() <= PAIRWISE_BLOCK {
+        // SAFETY: the caller guarantees that the lengths are equal
+        return unsafe { a.zip_eq_unchecked(b) }.fold(T::ZERO, |acc, (&a, &b)| acc + a * b);
+    }
+    let mid = a.len() / 2;
+    let (a_lo, a_hi) = a.split_at(mid);
+    let (b_lo, b_hi) = b.split_at(mid);
+    // SAFETY: `a` and `b` have the same length and are split at the same index
+    unsafe { dot_pairwise_unchecked(a_lo, b_lo) + dot_pairwise_unchecked(a_hi, b_hi) }
+}
+
+/// Returns the dot product of `a` and `b`, computed with pairwise summation.
+///
+/// The rounding error grows with the logarithm of the length instead of the length, at almost no
+/// cost.
+pub fn dot_pairwise<T: Float>(a: &[T], b: &[T]) -> T {
+    if a.len() != b.len() {
+        super::panic_different_len();
+    }
+    // SAFETY: the lengths were just checked
+    unsafe { dot_pairwise_unchecked(a, b) }
+}
+
+/// Returns the dot product of `a` and `b`, computed with Neumaier's compensated summation.
+///
+/// The rounding error of the sum doesn't grow with the length, but the loop is several times
+/// slower than [`dot`].
+///
+/// # Examples
+///
+/// ```
+/// let a = [1.0, 1e100, 1.0, -1e100];
+/// let ones = [1.0; 4];
+///
+/// assert_eq!(zip_eq::num::dot(&a, &ones), 0.0);
+/// assert_eq!(zip_eq::num::dot_compensated(&a, &ones), 2.0);
+/// ```
+pub fn dot_compensated<T: Float>(a: &[T], b: &[T]) -> T {
+    let (sum, compensation) =
+        a.zip_eq_eager(b)
+            .fold((T::ZERO, T::ZERO), |(sum, compensation), (&a, &b)| {
+                let x = a * b;
+                let t = sum + x;
+                let lost = if sum.abs() >= x.abs() {
+                    (sum - t) + x
+                } else {
+                    (x - t) + sum
+                };
+                (t, compensation + lost)
+            });
+    sum + compensation
+}
@@ -0,0 +1,294 @@
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, MultiZip, ParallelIterator};
+
+use super::{InputsMismatch, LengthMismatch, ZipEqLazyCheck};
+
+/// Parallel iterator that zips two indexed parallel iterators, checking that they have the same
+/// length during construction.
+///
+/// Both sides are always split at the same index, so each half still has two inputs of the same
+/// length.
+#[derive(Debug, Clone)]
+pub struct ParZipEqEager<A, B> {
+    a: A,
+    b: B,
+}
+
+/// Trait that adds `par_zip_eq_*` builder functions to objects that are convertible to indexed
+/// parallel iterators.
+pub trait ParZipEq: IntoParallelIterator<Iter: IndexedParallelIterator> + Sized {
+    /// Returns a zipped parallel iterator after checking that the lengths of the iterators are
+    /// equal. The check happens once, before any splitting.
+    /// # Panics
+    /// Panics if `a.len() != b.len()`, with both lengths in the message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use zip_eq::ParZipEq;
+    ///
+    /// let mut out = [0; 3];
+    /// let a = [1, 2, 3];
+    ///
+    /// out.par_iter_mut()
+    ///     .par_zip_eq_eager(&a)
+    ///     .for_each(|(o, a)| *o = 2 * a);
+    /// assert_eq!(out, [2, 4, 6]);
+    /// ```
+    fn par_zip_eq_eager<B>(self, b: B) -> ParZipEqEager<Self::Iter, B::Iter>
+    where
+        B: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        match self.try_par_zip_eq(b) {
+            Ok(zipped) => zipped,
+            Err(err) => panic_par_mismatch(err),
+        }
+    }
+
+    /// Returns a zipped parallel iterator if the lengths of the iterators are equal, or an error
+    /// describing the mismatch otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::ParZipEq;
+    ///
+    /// let a = vec![1, 2, 3];
+    /// let b = vec![3, 4];
+    ///
+    /// let err = a.try_par_zip_eq(b).unwrap_err();
+    /// assert_eq!((err.left_len(), err.right_len()), (Some(3), Some(2)));
+    /// ```
+    fn try_par_zip_eq<B>(self, b: B) -> Result<ParZipEqEager<Self::Iter, B::Iter>, LengthMismatch>
+    where
+        B: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let a = self.into_par_iter();
+        let b = b.into_par_iter();
+
+        let (a_len, b_len) = (a.len(), b.len());
+        if a_len != b_len {
+            return Err(LengthMismatch::new(a_len, b_len));
+        }
+        Ok(ParZipEqEager { a, b })
+    }
+}
+
+impl<A: IntoParallelIterator<Iter: IndexedParallelIterator>> ParZipEq for A {}
+
+#[cold]
+fn panic_par_mismatch(err: impl core::fmt::Display) -> ! {
+    panic!("ZipEq: {err}");
+}
+
+impl<A, B> ParallelIterator for ParZipEqEager<A, B>
+where
+    A: IndexedParallelIterator,
+    B: IndexedParallelIterator,
+{
+    type Item = (A::Item, B::Item);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.a.len())
+    }
+}
+
+impl<A, B> IndexedParallelIterator for ParZipEqEager<A, B>
+where
+    A: IndexedParallelIterator,
+    B: IndexedParallelIterator,
+{
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        return self.a.with_producer(CallbackA {
+            callback,
+            b: self.b,
+        });
+
+        struct CallbackA<CB, B> {
+            callback: CB,
+            b: B,
+        }
+
+        impl<CB, T, B> ProducerCallback<T> for CallbackA<CB, B>
+        where
+            B: IndexedParallelIterator,
+            CB: ProducerCallback<(T, B::Item)>,
+        {
+            type Output = CB::Output;
+
+            fn callback<A>(self, a: A) -> Self::Output
+            where
+                A: Producer<Item = T>,
+            {
+                self.b.with_producer(CallbackB {
+                    a,
+                    callback: self.callback,
+                })
+            }
+        }
+
+        struct CallbackB<CB, A> {
+            a: A,
+            callback: CB,
+        }
+
+        impl<CB, T, A> ProducerCallback<T> for CallbackB<CB, A>
+        where
+            A: Producer,
+            CB: ProducerCallback<(A::Item, T)>,
+        {
+            type Output = CB::Output;
+
+            fn callback<B>(self, b: B) -> Self::Output
+            where
+                B: Producer<Item = T>,
+            {
+                self.callback.callback(ZipEqProducer { a: self.a, b })
+            }
+        }
+    }
+}
+
+/// Producer that splits both of its sides at the same index.
+///
+/// [`Producer`] is a safe trait, so the lengths of the sequential iterators aren't trusted and
+/// are checked during iteration.
+struct ZipEqProducer<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Producer, B: Producer> Producer for ZipEqProducer<A, B> {
+    type Item = (A::Item, B::Item);
+    type IntoIter = ZipEqLazyCheck<A::IntoIter, B::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ZipEqLazyCheck {
+            a: self.a.into_iter(),
+            b: self.b.into_iter(),
+        }
+    }
+
+    fn min_len(&self) -> usize {
+        self.a.min_len().max(self.b.min_len())
+    }
+
+    fn max_len(&self) -> usize {
+        self.a.max_len().min(self.b.max_len())
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (a_left, a_right) = self.a.split_at(index);
+        let (b_left, b_right) = self.b.split_at(index);
+        (
+            ZipEqProducer {
+                a: a_left,
+                b: b_left,
+            },
+            ZipEqProducer {
+                a: a_right,
+                b: b_right,
+            },
+        )
+    }
+}
+
+/// Trait that adds N-ary parallel zipping to tuples of objects that are convertible to indexed
+/// parallel iterators.
+pub trait ParZipEqAll: Sized {
+    /// Zipped parallel iterator.
+    type Iter: IndexedParallelIterator;
+
+    /// Returns a parallel iterator over tuples of items, after checking that the lengths of all
+    /// the iterators are equal. The check happens once, before any splitting.
+    /// # Panics
+    /// Panics if the iterators don't all have the same length, with the mismatch in the message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use zip_eq::ParZipEqAll;
+    ///
+    /// let mut out = [0; 3];
+    /// let a = [1, 2, 3];
+    /// let b = [4, 5, 6];
+    ///
+    /// (&mut out, &a, &b)
+    ///     .par_zip_eq_all()
+    ///     .for_each(|(o, a, b)| *o = a + b);
+    /// assert_eq!(out, [5, 7, 9]);
+    /// ```
+    fn par_zip_eq_all(self) -> Self::Iter {
+        match self.try_par_zip_eq_all() {
+            Ok(zipped) => zipped,
+            Err(err) => panic_par_mismatch(err),
+        }
+    }
+
+    /// Returns a parallel iterator over tuples of items if the lengths of all the iterators are
+    /// equal, or an error describing the mismatch otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::ParZipEqAll;
+    ///
+    /// let err = (vec![1, 2], vec![3, 4, 5], vec![6]).try_par_zip_eq_all().unwrap_err();
+    /// assert_eq!((err.index(), err.ended(), err.continued()), (1, 2, 0));
+    /// ```
+    fn try_par_zip_eq_all(self) -> Result<Self::Iter, InputsMismatch>;
+}
+
+macro_rules! par_zip_eq_all_impls {
+    ($(($($T:ident),+))+) => {
+        $(
+            impl<$($T,)+> ParZipEqAll for ($($T,)+)
+            where
+                $($T: IntoParallelIterator<Iter: IndexedParallelIterator>,)+
+            {
+                type Iter = MultiZip<($($T::Iter,)+)>;
+
+                #[allow(non_snake_case)]
+                fn try_par_zip_eq_all(self) -> Result<Self::Iter, InputsMismatch> {
+                    let ($($T,)+) = self;
+                    let ($($T,)+) = ($($T.into_par_iter(),)+);
+
+                    if let Some(err) = InputsMismatch::from_lens(&[$($T.len(),)+]) {
+                        return Err(err);
+                    }
+                    Ok(($($T,)+).into_par_iter())
+                }
+            }
+        )+
+    };
+}
+
+par_zip_eq_all_impls! {
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+}
@@ -0,0 +1,315 @@
+use core::cmp::Ordering;
+
+/// Mutable slice-like containers whose elements can be reordered by [`apply_permutation`],
+/// [`co_shuffle`] and the `co_sort*` functions.
+pub trait CoSlice {
+    /// Element type of the slice.
+    type Item;
+
+    /// Returns the elements as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [Self::Item];
+}
+
+impl<T> CoSlice for &mut [T] {
+    type Item = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> CoSlice for &mut [T; N] {
+    type Item = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self[..]
+    }
+}
+
+impl<T> CoSlice for &mut Vec<T> {
+    type Item = T;
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// Sets of slices of the same length that are reordered together.
+///
+/// It is implemented for every [`CoSlice`], and for tuples of 2 to 6 of them.
+pub trait CoSlices {
+    /// Returns the length of the slices.
+    /// # Panics
+    /// Panics if the slices don't all have the same length.
+    fn common_len(&mut self) -> usize;
+
+    /// Swaps the elements at `i` and `j` in every slice.
+    fn swap(&mut self, i: usize, j: usize);
+}
+
+impl<S: CoSlice> CoSlices for S {
+    fn common_len(&mut self) -> usize {
+        self.as_mut_slice().len()
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        self.as_mut_slice().swap(i, j);
+    }
+}
+
+macro_rules! co_slices {
+    ($($S:ident $s:ident),+) => {
+        impl<$($S: CoSlice),+> CoSlices for ($($S,)+) {
+            fn common_len(&mut self) -> usize {
+                let ($($s,)+) = self;
+                let lens = [$($s.as_mut_slice().len()),+];
+                if lens.iter().any(|&len| len != lens[0]) {
+                    super::panic_different_len();
+                }
+                lens[0]
+            }
+
+            #[inline]
+            fn swap(&mut self, i: usize, j: usize) {
+                let ($($s,)+) = self;
+                $($s.as_mut_slice().swap(i, j);)+
+            }
+        }
+    };
+}
+
+co_slices!(A a, B b);
+co_slices!(A a, B b, C c);
+co_slices!(A a, B b, C c, D d);
+co_slices!(A a, B b, C c, D d, E e);
+co_slices!(A a, B b, C c, D d, E e, F f);
+
+/// The keys being sorted, and the slices reordered with them.
+struct Keyed<'a, K, S> {
+    keys: &'a mut [K],
+    values: S,
+}
+
+impl<K, S: CoSlices> CoSlices for Keyed<'_, K, S> {
+    fn common_len(&mut self) -> usize {
+        if self.keys.len() != self.values.common_len() {
+            super::panic_different_len();
+        }
+        self.keys.len()
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        self.keys.swap(i, j);
+        self.values.swap(i, j);
+    }
+}
+
+/// Reorders the slices so that the element at index `i` of each slice is the one that was at
+/// index `perm[i]`.
+///
+/// The lengths are checked before anything is moved. The permutation is applied in place with
+/// swaps, using one flag per element to track the ones already in place.
+/// # Panics
+/// Panics if the slices and `perm` don't all have the same length, or if `perm` isn't a
+/// permutation of `0..perm.len()`. In the latter case, the slices may have been partially
+/// reordered.
+///
+/// # Examples
+///
+/// ```
+/// let mut names = ["a", "b", "c"];
+/// let mut ages = vec![30, 10, 20];
+/// zip_eq::apply_permutation(&[1, 2, 0], (&mut names, &mut ages));
+///
+/// assert_eq!(names, ["b", "c", "a"]);
+/// assert_eq!(ages, [10, 20, 30]);
+/// ```
+pub fn apply_permutation<S: CoSlices>(perm: &[usize], mut slices: S) {
+    let len = slices.common_len();
+    if perm.len() != len {
+        super::panic_different_len();
+    }
+    let mut placed = vec![false; len];
+    for start in 0..len {
+        if placed[start] {
+            continue;
+        }
+        placed[start] = true;
+        // Each swap moves the element wanted at `i` into place, and the one from `start` along
+        // the cycle.
+        let mut i = start;
+        loop {
+            let j = perm[i];
+            if j == start {
+                break;
+            }
+            assert!(
+                j < len && !placed[j],
+                "apply_permutation: `perm` is not a permutation"
+            );
+            slices.swap(i, j);
+            placed[j] = true;
+            i = j;
+        }
+    }
+}
+
+/// Source of randomness for [`co_shuffle`].
+///
+/// It is implemented for closures that take the bound and return the index.
+pub trait ShuffleRng {
+    /// Returns a uniformly distributed random index in `0..bound`. `bound` is never 0.
+    fn random_index(&mut self, bound: usize) -> usize;
+}
+
+impl<F: FnMut(usize) -> usize> ShuffleRng for F {
+    fn random_index(&mut self, bound: usize) -> usize {
+        self(bound)
+    }
+}
+
+/// Shuffles the slices with the same random permutation, using the Fisher-Yates algorithm.
+/// # Panics
+/// Panics if the slices don't all have the same length, or if `rng` returns an index out of
+/// bounds.
+///
+/// # Examples
+///
+/// ```
+/// let mut features = [[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]];
+/// let mut labels = [0, 1, 2];
+///
+/// // A tiny xorshift generator, any source of random numbers will do.
+/// let mut state = 0x2545f4914f6cdd1d_u64;
+/// let mut rng = |bound: usize| {
+///     state ^= state << 13;
+///     state ^= state >> 7;
+///     state ^= state << 17;
+///     (state % bound as u64) as usize
+/// };
+/// zip_eq::co_shuffle((&mut features, &mut labels), &mut rng);
+///
+/// for (feature, label) in features.iter().zip(labels) {
+///     assert_eq!(feature[0], 2.0 * label as f64);
+/// }
+/// ```
+pub fn co_shuffle<S: CoSlices, R: ShuffleRng + ?Sized>(mut slices: S, rng: &mut R) {
+    let len = slices.common_len();
+    for i in (1..len).rev() {
+        let j = rng.random_index(i + 1);
+        assert!(j <= i, "co_shuffle: the random index is out of bounds");
+        slices.swap(i, j);
+    }
+}
+
+fn co_sort_impl<K, S: CoSlices>(keys: &mut [K], values: S, sort: impl FnOnce(&mut [usize], &[K])) {
+    let mut keyed = Keyed { keys, values };
+    let len = keyed.common_len();
+    let mut perm: Vec<usize> = (0..len).collect();
+    sort(&mut perm, keyed.keys);
+    apply_permutation(&perm, keyed);
+}
+
+/// Sorts `keys`, and reorders `values` the same way. The sort is stable.
+///
+/// `values` can be a slice, or a tuple of slices to reorder several of them.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// let mut keys = [3, 1, 2];
+/// let mut values = vec!["c", "a", "b"];
+/// zip_eq::co_sort(&mut keys, &mut values);
+///
+/// assert_eq!(keys, [1, 2, 3]);
+/// assert_eq!(values, ["a", "b", "c"]);
+/// ```
+pub fn co_sort<K: Ord, S: CoSlices>(keys: &mut [K], values: S) {
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_by(|&i, &j| keys[i].cmp(&keys[j]))
+    });
+}
+
+/// Sorts `keys` with a comparison function, and reorders `values` the same way. The sort is
+/// stable.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+pub fn co_sort_by<K, S, F>(keys: &mut [K], values: S, mut compare: F)
+where
+    S: CoSlices,
+    F: FnMut(&K, &K) -> Ordering,
+{
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_by(|&i, &j| compare(&keys[i], &keys[j]))
+    });
+}
+
+/// Sorts `keys` with a key extraction function, and reorders `values` the same way. The sort is
+/// stable.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// let mut names = ["bob", "al", "eve"];
+/// let mut ids = [7, 8, 9];
+/// let mut flags = [true, false, true];
+/// zip_eq::co_sort_by_key(&mut names, (&mut ids, &mut flags), |name| name.len());
+///
+/// assert_eq!(names, ["al", "bob", "eve"]);
+/// assert_eq!(ids, [8, 7, 9]);
+/// assert_eq!(flags, [false, true, true]);
+/// ```
+pub fn co_sort_by_key<K, S, O, F>(keys: &mut [K], values: S, mut f: F)
+where
+    S: CoSlices,
+    O: Ord,
+    F: FnMut(&K) -> O,
+{
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_by_key(|&i| f(&keys[i]))
+    });
+}
+
+/// Sorts `keys`, and reorders `values` the same way. The sort is unstable.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+pub fn co_sort_unstable<K: Ord, S: CoSlices>(keys: &mut [K], values: S) {
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_unstable_by(|&i, &j| keys[i].cmp(&keys[j]))
+    });
+}
+
+/// Sorts `keys` with a comparison function, and reorders `values` the same way. The sort is
+/// unstable.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+pub fn co_sort_unstable_by<K, S, F>(keys: &mut [K], values: S, mut compare: F)
+where
+    S: CoSlices,
+    F: FnMut(&K, &K) -> Ordering,
+{
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_unstable_by(|&i, &j| compare(&keys[i], &keys[j]))
+    });
+}
+
+/// Sorts `keys` with a key extraction function, and reorders `values` the same way. The sort is
+/// unstable.
+/// # Panics
+/// Panics if `keys` and `values` don't all have the same length.
+pub fn co_sort_unstable_by_key<K, S, O, F>(keys: &mut [K], values: S, mut f: F)
+where
+    S: CoSlices,
+    O: Ord,
+    F: FnMut(&K) -> O,
+{
+    co_sort_impl(keys, values, |perm, keys| {
+        perm.sort_unstable_by_key(|&i| f(&keys[i]))
+    });
+}
@@ -0,0 +1,552 @@
+use core::iter::{Chain, FusedIterator, Map, Once, TrustedLen};
+use core::{array, option, slice};
+use std::collections::vec_deque;
+
+use super::eager::unreachable_unchecked;
+use super::ZipEqEagerCheck;
+
+/// Contiguous run of elements that can be split at an index, such as a slice.
+///
+/// # Safety
+/// [`SegmentPair`] zips two segments of the same length without checking, so implementations
+/// must uphold these invariants:
+/// - `len` returns the exact number of items yielded by `into_iter`.
+/// - For `mid <= self.len()`, `split_at(mid)` returns a segment of length `mid` holding the
+///   first `mid` items, followed by a segment of length `self.len() - mid` holding the others.
+pub unsafe trait Segment:
+    Sized + IntoIterator<IntoIter: TrustedLen + ExactSizeIterator>
+{
+    /// Returns the number of elements in the segment.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the segment contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Splits the segment into the elements before `mid` and the elements starting at `mid`.
+    /// # Panics
+    /// May panic if `mid > self.len()`.
+    fn split_at(self, mid: usize) -> (Self, Self);
+}
+
+unsafe impl<T> Segment for &[T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        <[T]>::split_at(self, mid)
+    }
+}
+
+unsafe impl<T> Segment for &mut [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        <[T]>::split_at_mut(self, mid)
+    }
+}
+
+/// Iterator whose remaining elements can be walked as a sequence of contiguous segments.
+///
+/// This allows [`ZipEqEagerCheck::fold_segments`] and the methods built on it to iterate over
+/// pairs of aligned contiguous segments, instead of going through the element-wise path which
+/// the compiler can't vectorize for containers like [`VecDeque`](std::collections::VecDeque).
+///
+/// # Safety
+/// The segments of a [`ZipEqEagerCheck`] are aligned without checking that both inputs have
+/// segments left, so the segments returned by `into_segments` must hold exactly the remaining
+/// items of the iterator, in iteration order. In particular, their lengths must add up to the
+/// number of remaining items.
+pub unsafe trait SegmentedIterator: Iterator + Sized {
+    /// Contiguous segment type, yielding the same items as the iterator.
+    type Segment: Segment<Item = Self::Item>;
+    /// Iterator over the segments.
+    type Segments: Iterator<Item = Self::Segment>;
+
+    /// Returns an iterator over the contiguous segments containing the remaining elements, in
+    /// iteration order.
+    fn into_segments(self) -> Self::Segments;
+}
+
+unsafe impl<'a, T> SegmentedIterator for slice::Iter<'a, T> {
+    type Segment = &'a [T];
+    type Segments = option::IntoIter<&'a [T]>;
+
+    fn into_segments(self) -> Self::Segments {
+        Some(self.as_slice()).into_iter()
+    }
+}
+
+unsafe impl<'a, T> SegmentedIterator for slice::IterMut<'a, T> {
+    type Segment = &'a mut [T];
+    type Segments = option::IntoIter<&'a mut [T]>;
+
+    fn into_segments(self) -> Self::Segments {
+        Some(self.into_slice()).into_iter()
+    }
+}
+
+unsafe impl<'a, T> SegmentedIterator for vec_deque::Iter<'a, T> {
+    type Segment = &'a [T];
+    type Segments = array::IntoIter<&'a [T], 2>;
+
+    fn into_segments(self) -> Self::Segments {
+        let (front, back) = self.as_slices();
+        [front, back].into_iter()
+    }
+}
+
+unsafe impl<'a, T> SegmentedIterator for vec_deque::IterMut<'a, T> {
+    type Segment = &'a mut [T];
+    type Segments = array::IntoIter<&'a mut [T], 2>;
+
+    fn into_segments(self) -> Self::Segments {
+        let (front, back) = self.into_slices();
+        [front, back].into_iter()
+    }
+}
+
+/// Pair of segments of the same length, yielding pairs of elements.
+///
+/// This is the segment type of a [`ZipEqEagerCheck`] of two segmented iterators, which allows
+/// zipped iterators to be zipped again without losing their segments.
+#[derive(Debug, Clone)]
+pub struct SegmentPair<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Segment, B: Segment> SegmentPair<A, B> {
+    /// Returns the two segments.
+    pub fn into_parts(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: Segment, B: Segment> IntoIterator for SegmentPair<A, B> {
+    type Item = (A::Item, B::Item);
+    type IntoIter = ZipEqEagerCheck<A::IntoIter, B::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // SAFETY: the segments have the same length, by the invariants of `Segment`
+        ZipEqEagerCheck {
+            a: self.a.into_iter(),
+            b: self.b.into_iter(),
+        }
+    }
+}
+
+// SAFETY: both segments have the same length and are split at the same index
+unsafe impl<A: Segment, B: Segment> Segment for SegmentPair<A, B> {
+    fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        // The invariants of `Segment` only cover `mid <= len`.
+        assert!(mid <= self.len(), "SegmentPair::split_at: mid > len");
+        let (a_head, a_tail) = self.a.split_at(mid);
+        let (b_head, b_tail) = self.b.split_at(mid);
+        (
+            SegmentPair {
+                a: a_head,
+                b: b_head,
+            },
+            SegmentPair {
+                a: a_tail,
+                b: b_tail,
+            },
+        )
+    }
+}
+
+/// Iterator over pairs of aligned contiguous segments of two iterators of the same length.
+///
+/// Whenever the segment boundaries of the two iterators don't line up, the longer segment is
+/// split at the end of the shorter one. Empty segments are skipped.
+pub struct ZipSegments<A: SegmentedIterator, B: SegmentedIterator> {
+    a: A::Segments,
+    b: B::Segments,
+    rest_a: Option<A::Segment>,
+    rest_b: Option<B::Segment>,
+}
+
+#[inline]
+fn next_non_empty<S: Segment>(
+    rest: &mut Option<S>,
+    segments: &mut impl Iterator<Item = S>,
+) -> Option<S> {
+    // `is_empty` can be overridden, and isn't covered by the invariants of `Segment`.
+    rest.take().or_else(|| segments.find(|s| s.len() != 0))
+}
+
+// SAFETY: a and b have the same length, and so do their segments, by the invariants of
+// `SegmentedIterator` and `Segment`
+impl<A: SegmentedIterator, B: SegmentedIterator> Iterator for ZipSegments<A, B> {
+    type Item = SegmentPair<A::Segment, B::Segment>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = next_non_empty(&mut self.rest_a, &mut self.a)?;
+        let b = match next_non_empty(&mut self.rest_b, &mut self.b) {
+            Some(b) => b,
+            None => unsafe { unreachable_unchecked() },
+        };
+
+        let mid = a.len().min(b.len());
+        let (a, tail_a) = a.split_at(mid);
+        let (b, tail_b) = b.split_at(mid);
+        if tail_a.len() != 0 {
+            self.rest_a = Some(tail_a);
+        }
+        if tail_b.len() != 0 {
+            self.rest_b = Some(tail_b);
+        }
+        Some(SegmentPair { a, b })
+    }
+}
+
+impl<A: SegmentedIterator, B: SegmentedIterator> FusedIterator for ZipSegments<A, B> {}
+
+// SAFETY: the inputs have the same length, and `ZipSegments` splits both at the same indices
+unsafe impl<A: SegmentedIterator, B: SegmentedIterator> SegmentedIterator
+    for ZipEqEagerCheck<A, B>
+{
+    type Segment = SegmentPair<A::Segment, B::Segment>;
+    type Segments = ZipSegments<A, B>;
+
+    fn into_segments(self) -> Self::Segments {
+        ZipSegments {
+            a: self.a.into_segments(),
+            b: self.b.into_segments(),
+            rest_a: None,
+            rest_b: None,
+        }
+    }
+}
+
+impl<A: SegmentedIterator, B: SegmentedIterator> ZipEqEagerCheck<A, B> {
+    /// Folds over pairs of aligned contiguous segments of the two iterators.
+    ///
+    /// Each pair passed to `f` contains segments of the same length. Whenever the segment
+    /// boundaries of the two iterators don't line up, the longer segment is split at the end
+    /// of the shorter one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    /// use zip_eq::ZipEq;
+    ///
+    /// let mut a: VecDeque<_> = [2, 3, 4].into();
+    /// a.push_front(1);
+    /// let b = [5, 6, 7, 8];
+    ///
+    /// let lengths = a.iter().zip_eq_eager(&b).fold_segments(vec![], |mut acc, a, b| {
+    ///     assert_eq!(a.len(), b.len());
+    ///     acc.push(a.len());
+    ///     acc
+    /// });
+    /// assert_eq!(lengths.iter().sum::<usize>(), 4);
+    /// ```
+    #[inline]
+    pub fn fold_segments<I, F>(self, init: I, mut f: F) -> I
+    where
+        F: FnMut(I, A::Segment, B::Segment) -> I,
+    {
+        self.into_segments()
+            .fold(init, move |acc, pair| f(acc, pair.a, pair.b))
+    }
+
+    /// Folds every element pair into an accumulator, like [`Iterator::fold`], walking pairs of
+    /// aligned contiguous segments so the inner loop can be vectorized.
+    ///
+    /// [`Iterator::fold`] itself can't use the segments. `min_specialization` only allows
+    /// specializing on traits marked as specialization traits, which needs the internal
+    /// `rustc_attrs` feature. Such traits also can't have impls that constrain associated types,
+    /// like the one of [`SegmentChain`].
+    #[inline]
+    pub fn fold_segmented<I, F>(self, init: I, mut f: F) -> I
+    where
+        F: FnMut(I, (A::Item, B::Item)) -> I,
+    {
+        self.into_segments()
+            .fold(init, move |acc, pair| pair.into_iter().fold(acc, &mut f))
+    }
+
+    /// Calls a closure on every element pair, like [`Iterator::for_each`], walking pairs of
+    /// aligned contiguous segments so the inner loop can be vectorized.
+    #[inline]
+    pub fn for_each_segmented<F>(self, mut f: F)
+    where
+        F: FnMut((A::Item, B::Item)),
+    {
+        self.fold_segmented((), move |(), item| f(item));
+    }
+}
+
+/// Iterator that chains two segmented iterators, created by [`chain_segments`].
+///
+/// Unlike [`core::iter::Chain`], it exposes the segments of both of its halves and implements
+/// [`ExactSizeIterator`].
+#[derive(Debug, Clone)]
+pub struct SegmentChain<A, B> {
+    a: A,
+    b: B,
+}
+
+/// Chains two fused iterators yielding the same items, such that the segments of the result are
+/// the segments of `a` followed by the segments of `b`.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ZipEq;
+///
+/// let a = zip_eq::chain_segments([1, 2].iter(), [3].iter());
+/// let b = zip_eq::chain_segments([4].iter(), [5, 6].iter());
+///
+/// let mut sum = 0;
+/// a.zip_eq_eager(b).for_each_segmented(|(a, b)| sum += a * b);
+/// assert_eq!(sum, 32);
+/// ```
+pub fn chain_segments<A, B>(a: A, b: B) -> SegmentChain<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator<Item = A::Item>,
+{
+    SegmentChain {
+        a: a.into_iter(),
+        b: b.into_iter(),
+    }
+}
+
+impl<A, B> Iterator for SegmentChain<A, B>
+where
+    A: FusedIterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.a.next() {
+            None => self.b.next(),
+            item => item,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.size_hint();
+        let (b_lo, b_hi) = self.b.size_hint();
+        (
+            a_lo.saturating_add(b_lo),
+            match (a_hi, b_hi) {
+                (Some(a), Some(b)) => a.checked_add(b),
+                _ => None,
+            },
+        )
+    }
+
+    #[inline]
+    fn fold<I, F: FnMut(I, Self::Item) -> I>(self, init: I, mut f: F) -> I {
+        let acc = self.a.fold(init, &mut f);
+        self.b.fold(acc, f)
+    }
+}
+
+impl<A, B> DoubleEndedIterator for SegmentChain<A, B>
+where
+    A: FusedIterator + DoubleEndedIterator,
+    B: FusedIterator<Item = A::Item> + DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.b.next_back() {
+            None => self.a.next_back(),
+            item => item,
+        }
+    }
+}
+
+impl<A, B> ExactSizeIterator for SegmentChain<A, B>
+where
+    A: FusedIterator + ExactSizeIterator,
+    B: ExactSizeIterator<Item = A::Item>,
+{
+}
+
+unsafe impl<A, B> TrustedLen for SegmentChain<A, B>
+where
+    A: FusedIterator + TrustedLen,
+    B: TrustedLen<Item = A::Item>,
+{
+}
+impl<A, B> FusedIterator for SegmentChain<A, B>
+where
+    A: FusedIterator,
+    B: FusedIterator<Item = A::Item>,
+{
+}
+
+// SAFETY: `a` is fused, so its segments are followed by the items of `b`
+unsafe impl<A, B> SegmentedIterator for SegmentChain<A, B>
+where
+    A: FusedIterator + SegmentedIterator,
+    B: SegmentedIterator<Item = A::Item, Segment = A::Segment>,
+{
+    type Segment = A::Segment;
+    type Segments = Chain<A::Segments, B::Segments>;
+
+    fn into_segments(self) -> Self::Segments {
+        self.a.into_segments().chain(self.b.into_segments())
+    }
+}
+
+/// Iterator over the elements of a slice of slices, created by [`flatten_segments`].
+#[derive(Debug, Clone)]
+pub struct FlattenSegments<'a, S, T> {
+    outer: slice::Iter<'a, S>,
+    inner: slice::Iter<'a, T>,
+    len: usize,
+}
+
+/// Returns an iterator over the elements of the inner slices of `outer` (e.g. a `Vec<Vec<T>>`),
+/// with one segment per inner slice.
+///
+/// It implements [`TrustedLen`] and [`SegmentedIterator`] only when the inner containers are
+/// `Vec<T>`, `Box<[T]>` or `&[T]`, since other [`AsRef`] implementations could return slices of
+/// different lengths on each call.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ZipEq;
+///
+/// let rows = vec![vec![1, 2], vec![3, 4, 5]];
+/// let mut flat = vec![0; 5];
+///
+/// flat.iter_mut()
+///     .zip_eq_eager(zip_eq::flatten_segments(&rows))
+///     .for_each_segmented(|(o, x)| *o = 2 * *x);
+/// assert_eq!(flat, [2, 4, 6, 8, 10]);
+/// ```
+pub fn flatten_segments<S: AsRef<[T]>, T>(outer: &[S]) -> FlattenSegments<'_, S, T> {
+    FlattenSegments {
+        outer: outer.iter(),
+        inner: [].iter(),
+        len: outer.iter().map(|s| s.as_ref().len()).sum(),
+    }
+}
+
+impl<'a, S: AsRef<[T]>, T> Iterator for FlattenSegments<'a, S, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.inner.next() {
+                self.len = self.len.saturating_sub(1);
+                return Some(item);
+            }
+            self.inner = self.outer.next()?.as_ref().iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<S: AsRef<[T]>, T> ExactSizeIterator for FlattenSegments<'_, S, T> {}
+impl<S: AsRef<[T]>, T> FusedIterator for FlattenSegments<'_, S, T> {}
+
+// The length and the segments come from separate calls to `as_ref`, so they can only be trusted
+// for inner containers whose `as_ref` always returns the same slice.
+macro_rules! trusted_flatten_segments {
+    ($(<$($lt:lifetime)?> $S:ty),+) => {$(
+        // SAFETY: `as_ref` returns the same slice every time
+        unsafe impl<$($lt,)? T> TrustedLen for FlattenSegments<'_, $S, T> {}
+
+        // SAFETY: the segments are the rest of the current inner slice followed by the
+        // remaining inner slices
+        unsafe impl<'a, $($lt,)? T> SegmentedIterator for FlattenSegments<'a, $S, T> {
+            type Segment = &'a [T];
+            type Segments = Chain<Once<&'a [T]>, Map<slice::Iter<'a, $S>, fn(&'a $S) -> &'a [T]>>;
+
+            fn into_segments(self) -> Self::Segments {
+                core::iter::once(self.inner.as_slice())
+                    .chain(self.outer.map(<$S>::as_ref as fn(&'a $S) -> &'a [T]))
+            }
+        }
+    )+};
+}
+
+trusted_flatten_segments!(<> Vec<T>, <> Box<[T]>, <'s> &'s [T]);
+
+/// Iterator over the elements of a mutable slice of slices, created by
+/// [`flatten_segments_mut`].
+#[derive(Debug)]
+pub struct FlattenSegmentsMut<'a, S, T> {
+    outer: slice::IterMut<'a, S>,
+    inner: slice::IterMut<'a, T>,
+    len: usize,
+}
+
+/// Returns an iterator over mutable references to the elements of the inner slices of `outer`
+/// (e.g. a `Vec<Vec<T>>`), with one segment per inner slice.
+///
+/// As with [`flatten_segments`], it implements [`TrustedLen`] and [`SegmentedIterator`] only
+/// when the inner containers are `Vec<T>`, `Box<[T]>` or `&mut [T]`.
+pub fn flatten_segments_mut<S: AsMut<[T]>, T>(outer: &mut [S]) -> FlattenSegmentsMut<'_, S, T> {
+    let len = outer.iter_mut().map(|s| s.as_mut().len()).sum();
+    FlattenSegmentsMut {
+        outer: outer.iter_mut(),
+        inner: [].iter_mut(),
+        len,
+    }
+}
+
+impl<'a, S: AsMut<[T]>, T> Iterator for FlattenSegmentsMut<'a, S, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.inner.next() {
+                self.len = self.len.saturating_sub(1);
+                return Some(item);
+            }
+            self.inner = self.outer.next()?.as_mut().iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<S: AsMut<[T]>, T> ExactSizeIterator for FlattenSegmentsMut<'_, S, T> {}
+impl<S: AsMut<[T]>, T> FusedIterator for FlattenSegmentsMut<'_, S, T> {}
+
+macro_rules! trusted_flatten_segments_mut {
+    ($(<$($lt:lifetime)?> $S:ty),+) => {$(
+        // SAFETY: `as_mut` returns the same slice every time
+        unsafe impl<$($lt,)? T> TrustedLen for FlattenSegmentsMut<'_, $S, T> {}
+
+        // SAFETY: the segments are the rest of the current inner slice followed by the
+        // remaining inner slices
+        unsafe impl<'a, $($lt,)? T> SegmentedIterator for FlattenSegmentsMut<'a, $S, T> {
+            type Segment = &'a mut [T];
+            type Segments =
+                Chain<Once<&'a mut [T]>, Map<slice::IterMut<'a, $S>, fn(&'a mut $S) -> &'a mut [T]>>;
+
+            fn into_segments(self) -> Self::Segments {
+                core::iter::once(self.inner.into_slice())
+                    .chain(self.outer.map(<$S>::as_mut as fn(&'a mut $S) -> &'a mut [T]))
+            }
+        }
+    )+};
+}
+
+trusted_flatten_segments_mut!(<> Vec<T>, <> Box<[T]>, <'s> &'s mut [T]);
@@ -0,0 +1,294 @@
+use core::async_iter::AsyncIterator;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+#[cfg(feature = "futures")]
+use futures_core::{FusedStream, Stream};
+
+use super::{LengthMismatch, Side};
+
+#[derive(Debug, Clone)]
+enum Slot<T> {
+    Empty,
+    Ready(T),
+    Ended,
+}
+
+impl<T> Slot<T> {
+    #[inline]
+    fn fill(
+        &mut self,
+        poll: impl FnOnce(&mut Context<'_>) -> Poll<Option<T>>,
+        cx: &mut Context<'_>,
+    ) {
+        if let Slot::Empty = self {
+            match poll(cx) {
+                Poll::Ready(Some(item)) => *self = Slot::Ready(item),
+                Poll::Ready(None) => *self = Slot::Ended,
+                Poll::Pending => {}
+            }
+        }
+    }
+}
+
+/// Items polled from both sides, waiting for their counterpart.
+#[derive(Debug, Clone)]
+struct Slots<T, U> {
+    a: Slot<T>,
+    b: Slot<U>,
+    index: usize,
+    done: bool,
+}
+
+impl<T, U> Slots<T, U> {
+    fn new() -> Self {
+        Slots {
+            a: Slot::Empty,
+            b: Slot::Empty,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Polls both sides unless they already have an item or ended, and returns the next pair
+    /// once both sides have one. Sides whose item is waiting aren't polled again, so neither side
+    /// gets ahead of the other by more than one item.
+    #[inline]
+    fn poll(
+        &mut self,
+        poll_a: impl FnOnce(&mut Context<'_>) -> Poll<Option<T>>,
+        poll_b: impl FnOnce(&mut Context<'_>) -> Poll<Option<U>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<(T, U), LengthMismatch>>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        self.a.fill(poll_a, cx);
+        self.b.fill(poll_b, cx);
+
+        let longer = match (&self.a, &self.b) {
+            (Slot::Ready(_), Slot::Ready(_)) => {
+                let a = core::mem::replace(&mut self.a, Slot::Empty);
+                let b = core::mem::replace(&mut self.b, Slot::Empty);
+                return match (a, b) {
+                    (Slot::Ready(a), Slot::Ready(b)) => {
+                        self.index += 1;
+                        Poll::Ready(Some(Ok((a, b))))
+                    }
+                    _ => unreachable!(),
+                };
+            }
+            (Slot::Ended, Slot::Ended) => {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+            (Slot::Ready(_), Slot::Ended) => Side::Left,
+            (Slot::Ended, Slot::Ready(_)) => Side::Right,
+            _ => return Poll::Pending,
+        };
+        self.done = true;
+        Poll::Ready(Some(Err(LengthMismatch::at(self.index, longer))))
+    }
+
+    /// Adds the items waiting in the slots to the size hints of the sides.
+    fn size_hints(
+        &self,
+        a: (usize, Option<usize>),
+        b: (usize, Option<usize>),
+    ) -> ((usize, Option<usize>), (usize, Option<usize>)) {
+        fn add<T>(slot: &Slot<T>, (lo, hi): (usize, Option<usize>)) -> (usize, Option<usize>) {
+            match slot {
+                Slot::Empty => (lo, hi),
+                Slot::Ready(_) => (lo.saturating_add(1), hi.and_then(|hi| hi.checked_add(1))),
+                Slot::Ended => (0, Some(0)),
+            }
+        }
+        if self.done {
+            return ((0, Some(0)), (0, Some(0)));
+        }
+        (add(&self.a, a), add(&self.b, b))
+    }
+}
+
+/// Stream that zips two streams, checking that they have the same length while it's being
+/// polled.
+///
+/// # Panics
+/// Polling panics if one stream ends while the other one still has items.
+#[cfg(feature = "futures")]
+#[derive(Debug, Clone)]
+#[must_use = "streams do nothing unless polled"]
+pub struct StreamZipEq<A: Stream, B: Stream> {
+    a: A,
+    b: B,
+    slots: Slots<A::Item, B::Item>,
+}
+
+/// Stream that zips two streams, yielding an error item if one stream ends while the other one
+/// still has items.
+///
+/// The stream ends after yielding the error.
+#[cfg(feature = "futures")]
+#[derive(Debug, Clone)]
+#[must_use = "streams do nothing unless polled"]
+pub struct StreamZipEqChecked<A: Stream, B: Stream> {
+    a: A,
+    b: B,
+    slots: Slots<A::Item, B::Item>,
+}
+
+/// Returns a stream that zips `a` and `b`, checking that they have the same length while it's
+/// being polled, similarly to [`ZipEq::zip_eq_lazy`](super::ZipEq::zip_eq_lazy).
+#[cfg(feature = "futures")]
+pub fn zip_eq_stream<A: Stream, B: Stream>(a: A, b: B) -> StreamZipEq<A, B> {
+    StreamZipEq {
+        a,
+        b,
+        slots: Slots::new(),
+    }
+}
+
+/// Returns a stream that zips `a` and `b`, yielding `Err` if one of them ends while the other one
+/// still has items.
+#[cfg(feature = "futures")]
+pub fn zip_eq_stream_checked<A: Stream, B: Stream>(a: A, b: B) -> StreamZipEqChecked<A, B> {
+    StreamZipEqChecked {
+        a,
+        b,
+        slots: Slots::new(),
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<A: Stream, B: Stream> Stream for StreamZipEqChecked<A, B> {
+    type Item = Result<(A::Item, B::Item), LengthMismatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: a and b are structurally pinned, and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        let (a, b) = unsafe {
+            (
+                Pin::new_unchecked(&mut this.a),
+                Pin::new_unchecked(&mut this.b),
+            )
+        };
+        this.slots
+            .poll(|cx| a.poll_next(cx), |cx| b.poll_next(cx), cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = self
+            .slots
+            .size_hints(self.a.size_hint(), self.b.size_hint());
+        // Without assuming that the lengths are equal, the mismatch error is one more item.
+        (
+            a.0.min(b.0),
+            match (a.1, b.1) {
+                (Some(a), Some(b)) => a.min(b).checked_add(1),
+                (Some(a), None) => a.checked_add(1),
+                (None, Some(b)) => b.checked_add(1),
+                (None, None) => None,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<A: Stream, B: Stream> FusedStream for StreamZipEqChecked<A, B> {
+    fn is_terminated(&self) -> bool {
+        self.slots.done
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<A: Stream, B: Stream> Stream for StreamZipEq<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: a and b are structurally pinned, and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        let (a, b) = unsafe {
+            (
+                Pin::new_unchecked(&mut this.a),
+                Pin::new_unchecked(&mut this.b),
+            )
+        };
+        match this
+            .slots
+            .poll(|cx| a.poll_next(cx), |cx| b.poll_next(cx), cx)
+        {
+            Poll::Ready(Some(Err(_))) => super::panic_different_len(),
+            Poll::Ready(Some(Ok(pair))) => Poll::Ready(Some(pair)),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = self
+            .slots
+            .size_hints(self.a.size_hint(), self.b.size_hint());
+        super::size_hint_impl(a, b)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<A: Stream, B: Stream> FusedStream for StreamZipEq<A, B> {
+    fn is_terminated(&self) -> bool {
+        self.slots.done
+    }
+}
+
+/// Async iterator that zips two async iterators, checking that they have the same length while
+/// it's being polled.
+///
+/// # Panics
+/// Polling panics if one async iterator ends while the other one still has items.
+#[derive(Debug, Clone)]
+#[must_use = "async iterators do nothing unless polled"]
+pub struct AsyncIterZipEq<A: AsyncIterator, B: AsyncIterator> {
+    a: A,
+    b: B,
+    slots: Slots<A::Item, B::Item>,
+}
+
+/// Returns an async iterator that zips `a` and `b`, checking that they have the same length while
+/// it's being polled, similarly to [`ZipEq::zip_eq_lazy`](super::ZipEq::zip_eq_lazy).
+pub fn zip_eq_async_iter<A: AsyncIterator, B: AsyncIterator>(a: A, b: B) -> AsyncIterZipEq<A, B> {
+    AsyncIterZipEq {
+        a,
+        b,
+        slots: Slots::new(),
+    }
+}
+
+impl<A: AsyncIterator, B: AsyncIterator> AsyncIterator for AsyncIterZipEq<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: a and b are structurally pinned, and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        let (a, b) = unsafe {
+            (
+                Pin::new_unchecked(&mut this.a),
+                Pin::new_unchecked(&mut this.b),
+            )
+        };
+        match this
+            .slots
+            .poll(|cx| a.poll_next(cx), |cx| b.poll_next(cx), cx)
+        {
+            Poll::Ready(Some(Err(_))) => super::panic_different_len(),
+            Poll::Ready(Some(Ok(pair))) => Poll::Ready(Some(pair)),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = self
+            .slots
+            .size_hints(self.a.size_hint(), self.b.size_hint());
+        super::size_hint_impl(a, b)
+    }
+}
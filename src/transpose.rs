@@ -0,0 +1,144 @@
+use core::iter::FusedIterator;
+
+use super::{zip_eq_all, RaggedError, ZipEqAll};
+
+/// Turns rows into columns, after checking that all the rows have the same length.
+///
+/// The lengths are checked before anything is moved, so the error reports the first row whose
+/// length differs from the first row's. If `rows` is empty or its rows are empty, there are no
+/// columns.
+///
+/// # Examples
+///
+/// ```
+/// let rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// let columns = zip_eq::transpose_eq(rows).unwrap();
+///
+/// assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+/// assert_eq!(zip_eq::transpose_eq(columns).unwrap(), [[1, 2, 3], [4, 5, 6]]);
+///
+/// let err = zip_eq::transpose_eq(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+/// assert_eq!(err.to_string(), "row 2 has 1 elements but row 0 has 2");
+/// ```
+pub fn transpose_eq<T>(rows: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, RaggedError> {
+    let expected_len = rows.first().map_or(0, Vec::len);
+    if let Some(row) = rows.iter().position(|row| row.len() != expected_len) {
+        return Err(RaggedError::new(row, rows[row].len(), expected_len));
+    }
+    Ok(zip_eq_all(rows).collect())
+}
+
+/// Iterator over the columns of a sequence of rows, created by [`transpose_eq_lazy`].
+#[derive(Debug, Clone)]
+pub struct TransposeEq<I> {
+    zipped: ZipEqAll<I>,
+}
+
+/// Returns an iterator over the columns of `rows`, which zips the rows with [`zip_eq_all`] and
+/// checks that they have the same length during iteration.
+///
+/// A ragged row is found when the shortest rows end. As with [`transpose_eq`], the error reports
+/// the first row whose length differs from the first row's.
+///
+/// # Panics
+/// Iterating panics if the rows don't all have the same length. Use [`TransposeEq::try_next`]
+/// to get a [`RaggedError`] instead.
+///
+/// # Examples
+///
+/// ```
+/// let rows = [[1, 2], [3, 4], [5, 6]];
+/// let mut columns = zip_eq::transpose_eq_lazy(rows);
+///
+/// assert_eq!(columns.next(), Some(vec![1, 3, 5]));
+/// assert_eq!(columns.next(), Some(vec![2, 4, 6]));
+/// assert_eq!(columns.next(), None);
+/// ```
+///
+/// ```should_panic
+/// let rows = vec![vec![1, 2], vec![3, 4, 5]];
+/// // panics with "ZipEq: row 1 has 3 elements but row 0 has 2"
+/// zip_eq::transpose_eq_lazy(rows).for_each(drop);
+/// ```
+pub fn transpose_eq_lazy<R: IntoIterator>(
+    rows: impl IntoIterator<Item = R>,
+) -> TransposeEq<R::IntoIter> {
+    TransposeEq {
+        zipped: zip_eq_all(rows),
+    }
+}
+
+impl<I: Iterator> TransposeEq<I> {
+    /// Returns the next column, or an error if a row doesn't have the same length as the first
+    /// one. The iteration ends after the error.
+    ///
+    /// The rows that still had elements when the shortest ones ended are consumed, up to the
+    /// reported row, to count their elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut columns = zip_eq::transpose_eq_lazy([vec![1, 2, 3], vec![4, 5], vec![6]]);
+    ///
+    /// assert_eq!(columns.try_next(), Ok(Some(vec![1, 4, 6])));
+    /// let err = columns.try_next().unwrap_err();
+    /// assert_eq!((err.row(), err.row_len(), err.expected_len()), (1, 2, 3));
+    /// assert_eq!(columns.try_next(), Ok(None));
+    /// ```
+    pub fn try_next(&mut self) -> Result<Option<Vec<I::Item>>, RaggedError> {
+        let err = match self.zipped.try_next() {
+            Ok(column) => return Ok(column),
+            Err(err) => err,
+        };
+        // The first row either ended, so the first row that continued is the first ragged one,
+        // or continued, and the rows before the first one that ended may be longer or shorter.
+        let index = err.index();
+        let iters = &mut self.zipped.iters;
+        if err.ended() == 0 {
+            let row = err.continued();
+            let row_len = index + 1 + iters[row].by_ref().count();
+            return Err(RaggedError::new(row, row_len, index));
+        }
+        let expected_len = index + 1 + iters[0].by_ref().count();
+        for (row, iter) in iters.iter_mut().enumerate().take(err.ended()).skip(1) {
+            let row_len = index + 1 + iter.count();
+            if row_len != expected_len {
+                return Err(RaggedError::new(row, row_len, expected_len));
+            }
+        }
+        Err(RaggedError::new(err.ended(), index, expected_len))
+    }
+
+    /// Returns the rows, with the elements that were already yielded consumed.
+    pub fn into_inner(self) -> Vec<I> {
+        self.zipped.into_inner()
+    }
+}
+
+#[cold]
+fn panic_ragged(err: RaggedError) -> ! {
+    panic!("ZipEq: {err}");
+}
+
+impl<I: Iterator> Iterator for TransposeEq<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(column) => column,
+            Err(err) => panic_ragged(err),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.zipped.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for TransposeEq<I> {
+    fn len(&self) -> usize {
+        self.zipped.len()
+    }
+}
+
+impl<I: Iterator> FusedIterator for TransposeEq<I> {}
@@ -0,0 +1,144 @@
+use core::iter::TrustedLen;
+
+/// Collections that can be extended together by an iterator of tuples, one element of each tuple
+/// going to each collection.
+///
+/// It is implemented for tuples of 2 to 6 mutable references to collections.
+pub trait ExtendEq<T> {
+    /// Extends each collection with its element of the tuples of `iter`, after reserving room
+    /// for exactly as many elements as `iter` has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zip_eq::ExtendEq;
+    ///
+    /// let mut ids = vec![0];
+    /// let mut names = String::new();
+    /// let mut flags = Vec::new();
+    /// (&mut ids, &mut names, &mut flags).extend_eq([(1, 'a', true), (2, 'b', false)]);
+    ///
+    /// assert_eq!(ids, [0, 1, 2]);
+    /// assert_eq!(names, "ab");
+    /// assert_eq!(flags, [true, false]);
+    /// ```
+    fn extend_eq<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: TrustedLen;
+}
+
+/// Tuples of collections that can be built from an iterator of tuples, one element of each tuple
+/// going to each collection, similarly to [`Iterator::unzip`].
+///
+/// It is implemented for tuples of 2 to 6 collections, and is usually used through
+/// [`unzip_eq`].
+pub trait FromUnzipEq<T>: Sized {
+    /// Creates the collections from `iter`, after reserving room for exactly as many elements as
+    /// `iter` has in each of them.
+    fn from_unzip_eq<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: TrustedLen;
+}
+
+/// Returns the number of elements that a [`TrustedLen`] iterator yields, or `usize::MAX` if it
+/// yields more, which no collection can reserve room for anyway.
+#[inline]
+fn exact_len(iter: &impl TrustedLen) -> usize {
+    let (lower, upper) = iter.size_hint();
+    upper.unwrap_or(lower)
+}
+
+macro_rules! unzip_eq {
+    ($($T:ident $E:ident $e:ident $x:ident),+) => {
+        impl<$($T, $E: Extend<$T>),+> ExtendEq<($($T,)+)> for ($(&mut $E,)+) {
+            #[inline]
+            fn extend_eq<I>(&mut self, iter: I)
+            where
+                I: IntoIterator<Item = ($($T,)+)>,
+                I::IntoIter: TrustedLen,
+            {
+                let iter = iter.into_iter();
+                let len = exact_len(&iter);
+                let ($($e,)+) = self;
+                $($e.extend_reserve(len);)+
+                iter.for_each(|($($x,)+)| {
+                    $($e.extend_one($x);)+
+                });
+            }
+        }
+
+        impl<$($T, $E: Default + Extend<$T>),+> FromUnzipEq<($($T,)+)> for ($($E,)+) {
+            #[inline]
+            fn from_unzip_eq<I>(iter: I) -> Self
+            where
+                I: IntoIterator<Item = ($($T,)+)>,
+                I::IntoIter: TrustedLen,
+            {
+                let ($(mut $e,)+) = ($($E::default(),)+);
+                ($(&mut $e,)+).extend_eq(iter);
+                ($($e,)+)
+            }
+        }
+    };
+}
+
+unzip_eq!(A EA ea a, B EB eb b);
+unzip_eq!(A EA ea a, B EB eb b, C EC ec c);
+unzip_eq!(A EA ea a, B EB eb b, C EC ec c, D ED ed d);
+unzip_eq!(A EA ea a, B EB eb b, C EC ec c, D ED ed d, E EE ee e);
+unzip_eq!(A EA ea a, B EB eb b, C EC ec c, D ED ed d, E EE ee e, F EF ef f);
+
+/// Extends `a` and `b` with the elements of the pairs of `iter`, after reserving room for exactly
+/// as many elements as `iter` has in both of them.
+///
+/// Use [`ExtendEq`] to extend more than two collections.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ZipEq;
+///
+/// let mut sums = Vec::new();
+/// let mut products = Vec::new();
+/// let pairs = [1, 2, 3].zip_eq_eager([4, 5, 6]).map(|(a, b)| (a + b, a * b));
+/// zip_eq::extend_pairs(&mut sums, &mut products, pairs);
+///
+/// assert_eq!(sums, [5, 7, 9]);
+/// assert_eq!(products, [4, 10, 18]);
+/// ```
+pub fn extend_pairs<A, B, EA, EB, I>(a: &mut EA, b: &mut EB, iter: I)
+where
+    EA: Extend<A>,
+    EB: Extend<B>,
+    I: IntoIterator<Item = (A, B)>,
+    I::IntoIter: TrustedLen,
+{
+    (a, b).extend_eq(iter);
+}
+
+/// Splits an iterator of tuples into a tuple of collections, after reserving room for exactly as
+/// many elements as `iter` has in each of them.
+///
+/// Unlike [`Iterator::unzip`], this works for tuples of 2 to 6 elements, and reserves the exact
+/// length of `iter` since it is [`TrustedLen`].
+///
+/// # Examples
+///
+/// ```
+/// let (ids, names, flags): (Vec<_>, String, Vec<_>) =
+///     zip_eq::unzip_eq([(1, 'a', true), (2, 'b', false)]);
+///
+/// assert_eq!(ids, [1, 2]);
+/// assert_eq!(names, "ab");
+/// assert_eq!(flags, [true, false]);
+/// ```
+pub fn unzip_eq<T, C, I>(iter: I) -> C
+where
+    C: FromUnzipEq<T>,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: TrustedLen,
+{
+    C::from_unzip_eq(iter)
+}
@@ -0,0 +1,239 @@
+use core::ops::RangeBounds;
+use core::{ptr, slice};
+use std::vec;
+
+use super::ZipEqEagerCheck;
+
+#[inline]
+fn check_lens<A, B>(a: &[A], b: &[B]) {
+    if a.len() != b.len() {
+        super::panic_different_len();
+    }
+}
+
+/// State of an in-place compaction of two vectors. If it's dropped before the end, by a panic in
+/// a predicate or a destructor, it moves the unprocessed elements after the kept ones and gives
+/// both vectors the same length back.
+struct Compaction<'a, A, B> {
+    a: &'a mut Vec<A>,
+    b: &'a mut Vec<B>,
+    len: usize,
+    processed: usize,
+    deleted: usize,
+}
+
+impl<A, B> Drop for Compaction<'_, A, B> {
+    fn drop(&mut self) {
+        let remaining = self.len - self.processed;
+        if self.deleted > 0 && remaining > 0 {
+            // SAFETY: the unprocessed elements are initialized, and moved to right after the
+            // kept ones
+            unsafe {
+                let a = self.a.as_mut_ptr();
+                let b = self.b.as_mut_ptr();
+                ptr::copy(
+                    a.add(self.processed),
+                    a.add(self.processed - self.deleted),
+                    remaining,
+                );
+                ptr::copy(
+                    b.add(self.processed),
+                    b.add(self.processed - self.deleted),
+                    remaining,
+                );
+            }
+        }
+        // SAFETY: the first `len - deleted` elements are initialized
+        unsafe {
+            self.a.set_len(self.len - self.deleted);
+            self.b.set_len(self.len - self.deleted);
+        }
+    }
+}
+
+/// Removes the pairs for which `remove` returns `true`, keeping the order of the others. `remove`
+/// is given the pair at index `i`, and the last kept pair if any.
+fn compact<A, B>(
+    a: &mut Vec<A>,
+    b: &mut Vec<B>,
+    mut remove: impl FnMut((&mut A, &mut B), Option<(&mut A, &mut B)>) -> bool,
+) {
+    check_lens(a, b);
+    let len = a.len();
+    // Until the compaction ends, the vectors don't own their elements, so that forgetting it
+    // only leaks them.
+    // SAFETY: 0 is always a valid length
+    unsafe {
+        a.set_len(0);
+        b.set_len(0);
+    }
+    let mut state = Compaction {
+        a,
+        b,
+        len,
+        processed: 0,
+        deleted: 0,
+    };
+    let a_ptr = state.a.as_mut_ptr();
+    let b_ptr = state.b.as_mut_ptr();
+
+    while state.processed < len {
+        let i = state.processed;
+        let kept = i - state.deleted;
+        // SAFETY: the pair at `i` is initialized and not aliased, and the last kept pair is at
+        // `kept - 1 < i`
+        let remove = unsafe {
+            let last = (kept > 0).then(|| (&mut *a_ptr.add(kept - 1), &mut *b_ptr.add(kept - 1)));
+            remove((&mut *a_ptr.add(i), &mut *b_ptr.add(i)), last)
+        };
+        if remove {
+            // The pair counts as removed before being dropped, in case a destructor panics.
+            state.processed += 1;
+            state.deleted += 1;
+            // SAFETY: the pair at `i` is initialized, and never used again
+            unsafe {
+                ptr::drop_in_place(a_ptr.add(i));
+                ptr::drop_in_place(b_ptr.add(i));
+            }
+        } else {
+            if state.deleted > 0 {
+                // SAFETY: the pair at `kept` was moved or dropped, and `kept < i`
+                unsafe {
+                    ptr::copy_nonoverlapping(a_ptr.add(i), a_ptr.add(kept), 1);
+                    ptr::copy_nonoverlapping(b_ptr.add(i), b_ptr.add(kept), 1);
+                }
+            }
+            state.processed += 1;
+        }
+    }
+}
+
+/// Keeps only the pairs of elements of `a` and `b` for which `f` returns `true`, keeping their
+/// order.
+///
+/// If `f` or a destructor panics, the vectors keep the same length.
+/// # Panics
+/// Panics if `a.len() != b.len()`
+///
+/// # Examples
+///
+/// ```
+/// let mut names = vec!["a", "b", "c", "d"];
+/// let mut scores = vec![3, 8, 1, 9];
+/// zip_eq::retain_pairs(&mut names, &mut scores, |_, &score| score > 2);
+///
+/// assert_eq!(names, ["a", "b", "d"]);
+/// assert_eq!(scores, [3, 8, 9]);
+/// ```
+pub fn retain_pairs<A, B, F>(a: &mut Vec<A>, b: &mut Vec<B>, mut f: F)
+where
+    F: FnMut(&A, &B) -> bool,
+{
+    compact(a, b, |(a, b), _| !f(a, b));
+}
+
+/// Removes the consecutive pairs of elements of `a` and `b` for which `same_bucket` returns
+/// `true`, like [`Vec::dedup_by`].
+///
+/// `same_bucket` is given the current pair and the last pair that was kept, and the current pair
+/// is removed if it returns `true`. If `same_bucket` or a destructor panics, the vectors keep
+/// the same length.
+/// # Panics
+/// Panics if `a.len() != b.len()`
+///
+/// # Examples
+///
+/// ```
+/// let mut keys = vec![1, 1, 2, 2, 1];
+/// let mut counts = vec![1, 2, 3, 4, 5];
+/// zip_eq::dedup_pairs_by(&mut keys, &mut counts, |(key, count), (last_key, last_count)| {
+///     let same = key == last_key;
+///     if same {
+///         *last_count += *count;
+///     }
+///     same
+/// });
+///
+/// assert_eq!(keys, [1, 2, 1]);
+/// assert_eq!(counts, [3, 7, 5]);
+/// ```
+pub fn dedup_pairs_by<A, B, F>(a: &mut Vec<A>, b: &mut Vec<B>, mut same_bucket: F)
+where
+    F: FnMut((&mut A, &mut B), (&mut A, &mut B)) -> bool,
+{
+    compact(a, b, |current, last| match last {
+        Some(last) => same_bucket(current, last),
+        None => false,
+    });
+}
+
+/// Removes the pairs of elements of `a` and `b` in `range`, and returns them as an iterator.
+///
+/// Like [`Vec::drain`], the pairs that aren't consumed are dropped with the iterator, and the
+/// vectors keep the same length even if a destructor panics or the iterator is leaked.
+/// # Panics
+/// Panics if `a.len() != b.len()`, or if the range is out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// let mut a = vec![1, 2, 3, 4];
+/// let mut b = vec!['a', 'b', 'c', 'd'];
+/// let drained: Vec<_> = zip_eq::drain_pairs(&mut a, &mut b, 1..3).collect();
+///
+/// assert_eq!(drained, [(2, 'b'), (3, 'c')]);
+/// assert_eq!(a, [1, 4]);
+/// assert_eq!(b, ['a', 'd']);
+/// ```
+pub fn drain_pairs<'a, 'b, A, B, R>(
+    a: &'a mut Vec<A>,
+    b: &'b mut Vec<B>,
+    range: R,
+) -> ZipEqEagerCheck<vec::Drain<'a, A>, vec::Drain<'b, B>>
+where
+    R: RangeBounds<usize>,
+{
+    check_lens(a, b);
+    // The bounds are resolved once, since `range` could return different ones on each call.
+    let range = slice::range(range, ..a.len());
+    // SAFETY: the vectors have the same length and are drained over the same range, so the
+    // drains have the same length too
+    ZipEqEagerCheck {
+        a: a.drain(range.clone()),
+        b: b.drain(range),
+    }
+}
+
+/// Keeps the first `len` pairs of elements of `a` and `b`, and drops the others.
+///
+/// Both vectors are shortened before anything is dropped, so a panicking destructor can only
+/// leak the rest of the dropped elements.
+/// # Panics
+/// Panics if `a.len() != b.len()`
+///
+/// # Examples
+///
+/// ```
+/// let mut a = vec![1, 2, 3];
+/// let mut b = vec![4, 5, 6];
+/// zip_eq::truncate_pairs(&mut a, &mut b, 1);
+///
+/// assert_eq!((a, b), (vec![1], vec![4]));
+/// ```
+pub fn truncate_pairs<A, B>(a: &mut Vec<A>, b: &mut Vec<B>, len: usize) {
+    check_lens(a, b);
+    let old_len = a.len();
+    if len >= old_len {
+        return;
+    }
+    let a_tail = ptr::slice_from_raw_parts_mut(a.as_mut_ptr().wrapping_add(len), old_len - len);
+    let b_tail = ptr::slice_from_raw_parts_mut(b.as_mut_ptr().wrapping_add(len), old_len - len);
+    // SAFETY: the tails are initialized, and no longer owned by the vectors once they are
+    // shortened
+    unsafe {
+        a.set_len(len);
+        b.set_len(len);
+        ptr::drop_in_place(a_tail);
+        ptr::drop_in_place(b_tail);
+    }
+}
@@ -0,0 +1,247 @@
+//! Derive macros for the [`zip_eq`](https://docs.rs/zip_eq) crate.
+//!
+//! They are re-exported by `zip_eq` when its `derive` feature is enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, Ident,
+    Lifetime, PathArguments, Type, WherePredicate,
+};
+
+/// Derives row access for a struct whose fields are all `Vec`s of the same length.
+///
+/// For a struct `Batch`, this generates:
+/// - `Batch::validate_lengths(&self) -> Result<(), zip_eq::InputsMismatch>`, which checks that
+///   all the fields have the same length. The inputs of the error are the fields, numbered in
+///   declaration order.
+/// - `BatchRow<'_>` and `BatchRowMut<'_>`, with the same fields as `Batch` holding references
+///   to the elements of one row.
+/// - `Batch::rows(&self)` and `Batch::rows_mut(&mut self)`, which iterate over the rows using
+///   [`ZipEq::zip_eq_eager`](https://docs.rs/zip_eq/latest/zip_eq/trait.ZipEq.html).
+/// - `Batch::push_row(&mut self, ...)`, which takes one argument per field and appends them.
+///
+/// The generated items have the same visibility as the struct.
+///
+/// # Panics
+/// `rows` and `rows_mut` panic if the fields don't all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// use zip_eq::ZipEqRows;
+///
+/// #[derive(ZipEqRows, Default)]
+/// struct Batch {
+///     ids: Vec<u64>,
+///     scores: Vec<f32>,
+///     labels: Vec<u8>,
+/// }
+///
+/// let mut batch = Batch::default();
+/// batch.push_row(7, 0.5, 1);
+/// batch.push_row(8, 0.25, 0);
+///
+/// for row in batch.rows_mut() {
+///     *row.scores *= 2.0;
+/// }
+/// let row = batch.rows().last().unwrap();
+/// assert_eq!((*row.ids, *row.scores, *row.labels), (8, 0.5, 0));
+///
+/// batch.labels.pop();
+/// let err = batch.validate_lengths().unwrap_err();
+/// assert_eq!((err.ended(), err.continued()), (2, 0));
+/// ```
+#[proc_macro_derive(ZipEqRows)]
+pub fn derive_zip_eq_rows(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    rows(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Returns `T` if `ty` is `Vec<T>`.
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(element) if args.args.len() == 1 => Some(element),
+        _ => None,
+    }
+}
+
+fn rows(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "ZipEqRows requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ZipEqRows can only be derived for structs",
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "ZipEqRows requires at least one field",
+        ));
+    }
+
+    let names: Vec<&Ident> = fields.iter().filter_map(|f| f.ident.as_ref()).collect();
+    let field_vis: Vec<_> = fields.iter().map(|f| &f.vis).collect();
+    let elements = fields
+        .iter()
+        .map(|f| {
+            vec_element(&f.ty)
+                .ok_or_else(|| Error::new_spanned(&f.ty, "ZipEqRows fields must be `Vec`s"))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let row = format_ident!("{}Row", name);
+    let row_mut = format_ident!("{}RowMut", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let lifetime = Lifetime::new("'zip_eq_row", Span::call_site());
+    let mut row_generics = input.generics.clone();
+    row_generics.params.insert(0, parse_quote!(#lifetime));
+    let row_params = &row_generics.params;
+    let row_where_clause = &row_generics.where_clause;
+    let (row_impl_generics, row_ty_generics, _) = row_generics.split_for_impl();
+    // The bounds name the row's lifetime so that they aren't trivially false, and an error, when
+    // an element type isn't `Debug`. Such rows just don't implement `Debug`.
+    let mut debug_where_clause = row_generics.clone().make_where_clause().clone();
+    debug_where_clause.predicates.extend(
+        elements
+            .iter()
+            .map(|ty| -> WherePredicate { parse_quote!(&#lifetime #ty: ::core::fmt::Debug) }),
+    );
+    let mut debug_mut_where_clause = row_generics.clone().make_where_clause().clone();
+    debug_mut_where_clause.predicates.extend(
+        elements
+            .iter()
+            .map(|ty| -> WherePredicate { parse_quote!(&#lifetime mut #ty: ::core::fmt::Debug) }),
+    );
+    let mut anon_generics = input.generics.clone();
+    anon_generics.params.insert(0, parse_quote!('_));
+    let (_, anon_ty_generics, _) = anon_generics.split_for_impl();
+
+    // Zips the columns one after the other, then flattens the nested pairs into a row.
+    let first = names[0];
+    let mut pattern = quote!(#first);
+    let mut zip = quote!(self.#first.iter());
+    let mut zip_mut = quote!(self.#first.iter_mut());
+    for field in &names[1..] {
+        pattern = quote!((#pattern, #field));
+        zip = quote!(::zip_eq::ZipEq::zip_eq_eager(#zip, self.#field.iter()));
+        zip_mut = quote!(::zip_eq::ZipEq::zip_eq_eager(#zip_mut, self.#field.iter_mut()));
+    }
+    let row_doc = format!("References to the elements of one row of a [`{name}`].");
+    let row_mut_doc = format!("Mutable references to the elements of one row of a [`{name}`].");
+
+    // The rows are often used for a few of their fields only. Their impls are written by hand,
+    // since the derives would require the elements to implement the traits, even though the rows
+    // only hold references to them.
+    Ok(quote! {
+        #[doc = #row_doc]
+        #[allow(dead_code)]
+        #vis struct #row <#row_params> #row_where_clause {
+            #(#field_vis #names: &#lifetime #elements,)*
+        }
+
+        impl #row_impl_generics ::core::clone::Clone for #row #row_ty_generics #row_where_clause {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #row_impl_generics ::core::marker::Copy for #row #row_ty_generics #row_where_clause {}
+
+        impl #row_impl_generics ::core::fmt::Debug for #row #row_ty_generics #debug_where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!(#row))
+                    #(.field(::core::stringify!(#names), &self.#names))*
+                    .finish()
+            }
+        }
+
+        #[doc = #row_mut_doc]
+        #[allow(dead_code)]
+        #vis struct #row_mut <#row_params> #row_where_clause {
+            #(#field_vis #names: &#lifetime mut #elements,)*
+        }
+
+        impl #row_impl_generics ::core::fmt::Debug for #row_mut #row_ty_generics
+            #debug_mut_where_clause
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!(#row_mut))
+                    #(.field(::core::stringify!(#names), &self.#names))*
+                    .finish()
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Checks that all the fields have the same length.
+            #vis fn validate_lengths(&self) -> ::core::result::Result<(), ::zip_eq::InputsMismatch> {
+                match ::zip_eq::InputsMismatch::from_lens(&[#(self.#names.len()),*]) {
+                    ::core::option::Option::Some(err) => ::core::result::Result::Err(err),
+                    ::core::option::Option::None => ::core::result::Result::Ok(()),
+                }
+            }
+
+            /// Returns an iterator over references to the rows.
+            /// # Panics
+            /// Panics if the fields don't all have the same length.
+            #vis fn rows(
+                &self,
+            ) -> impl ::core::iter::ExactSizeIterator<Item = #row #anon_ty_generics>
+                   + ::core::iter::DoubleEndedIterator {
+                if let ::core::result::Result::Err(err) = self.validate_lengths() {
+                    ::core::panic!("{}: {}", ::core::stringify!(#name), err);
+                }
+                ::core::iter::Iterator::map(#zip, |#pattern| #row { #(#names),* })
+            }
+
+            /// Returns an iterator over mutable references to the rows.
+            /// # Panics
+            /// Panics if the fields don't all have the same length.
+            #vis fn rows_mut(
+                &mut self,
+            ) -> impl ::core::iter::ExactSizeIterator<Item = #row_mut #anon_ty_generics>
+                   + ::core::iter::DoubleEndedIterator {
+                if let ::core::result::Result::Err(err) = self.validate_lengths() {
+                    ::core::panic!("{}: {}", ::core::stringify!(#name), err);
+                }
+                ::core::iter::Iterator::map(#zip_mut, |#pattern| #row_mut { #(#names),* })
+            }
+
+            /// Appends a row, with one argument per field.
+            #[allow(clippy::too_many_arguments)]
+            #vis fn push_row(&mut self, #(#names: #elements),*) {
+                // Once the room is reserved, none of the pushes can panic.
+                #(self.#names.reserve(1);)*
+                #(self.#names.push(#names);)*
+            }
+        }
+    })
+}